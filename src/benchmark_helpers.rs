@@ -0,0 +1,103 @@
+//! Worst-case state for benchmarking the Stp258Standard module.
+//!
+//! Shared by this module's benchmarks and by runtimes benchmarking pallets
+//! built on top of it, so weights are measured against realistic upper
+//! bounds rather than empty storage.
+
+use super::*;
+
+/// A non-native currency of `Config::SampledCurrencies` to benchmark with.
+pub fn non_native_currency<T: Config<I>, I: 'static>() -> CurrencyIdOf<T, I> {
+	let native_currency_id = <Pallet<T, I>>::native_currency_id();
+	T::SampledCurrencies::get()
+		.into_iter()
+		.find(|currency_id| *currency_id != native_currency_id)
+		.expect("benchmarks need a non-native currency in `Config::SampledCurrencies`")
+}
+
+/// Deposit a balance of `currency_id` well above its existential deposit
+/// into `who`, returning the amount deposited.
+pub fn fund<T: Config<I>, I: 'static>(
+	currency_id: CurrencyIdOf<T, I>,
+	who: &T::AccountId,
+) -> result::Result<BalanceOf<T, I>, DispatchError> {
+	let amount = <Pallet<T, I> as Stp258Currency<T::AccountId>>::minimum_balance(currency_id)
+		.saturating_add(1_000_000_000_000u128.saturated_into());
+	<Pallet<T, I> as Stp258Currency<T::AccountId>>::deposit(currency_id, who, amount)?;
+	Ok(amount)
+}
+
+/// The lock prefix used by benchmark locks, registered on first use.
+pub const BENCHMARK_LOCK_PREFIX: LockPrefix = *b"bch";
+
+/// The `n`th benchmark lock identifier.
+pub fn benchmark_lock_id(n: u32) -> LockIdentifier {
+	lock_id::namespaced(BENCHMARK_LOCK_PREFIX, n)
+}
+
+/// Register `BENCHMARK_LOCK_PREFIX`, so benchmark locks take the namespaced
+/// lock path.
+pub fn register_benchmark_lock_prefix<T: Config<I>, I: 'static>() {
+	LockPrefixes::<T, I>::insert(BENCHMARK_LOCK_PREFIX, true);
+}
+
+/// Set `n` distinct locks of `amount` on `who` under `currency_id`.
+pub fn set_max_locks<T: Config<I>, I: 'static>(
+	currency_id: CurrencyIdOf<T, I>,
+	who: &T::AccountId,
+	amount: BalanceOf<T, I>,
+	n: u32,
+) -> DispatchResult {
+	register_benchmark_lock_prefix::<T, I>();
+	for i in 0..n {
+		<Pallet<T, I> as Stp258CurrencyLockable<T::AccountId>>::set_lock(
+			benchmark_lock_id(i),
+			currency_id,
+			who,
+			amount,
+		)?;
+	}
+	Ok(())
+}
+
+/// Queue `n` locks of `amount` on `who` under `currency_id` to expire at
+/// block `at`.
+pub fn fill_lock_expiry_queue<T: Config<I>, I: 'static>(
+	currency_id: CurrencyIdOf<T, I>,
+	who: &T::AccountId,
+	amount: BalanceOf<T, I>,
+	at: T::BlockNumber,
+	n: u32,
+) -> DispatchResult {
+	register_benchmark_lock_prefix::<T, I>();
+	for i in 0..n {
+		<Pallet<T, I>>::set_lock_until(benchmark_lock_id(i), currency_id, who, amount, at)?;
+	}
+	Ok(())
+}
+
+/// Announce `n` balance updates of `amount` on `who` under `currency_id`,
+/// unlocked from the current block.
+pub fn fill_pending_updates<T: Config<I>, I: 'static>(
+	currency_id: CurrencyIdOf<T, I>,
+	who: &T::AccountId,
+	amount: AmountOf<T, I>,
+	n: u32,
+) -> DispatchResult {
+	let unlock_at = <frame_system::Module<T>>::block_number();
+	for _ in 0..n {
+		let id = <Pallet<T, I>>::next_update_id();
+		let next_id = id.checked_add(1).ok_or(Error::<T, I>::NoAvailableUpdateId)?;
+		NextUpdateId::<T, I>::put(next_id);
+		PendingUpdates::<T, I>::insert(
+			id,
+			PendingUpdate {
+				who: who.clone(),
+				currency_id,
+				amount,
+				unlock_at,
+			},
+		);
+	}
+	Ok(())
+}
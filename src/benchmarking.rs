@@ -0,0 +1,206 @@
+//! Benchmarks for the Stp258Standard module.
+//!
+//! The set up uses `benchmark_helpers`, so each call is measured against
+//! the worst-case state it can meet.
+
+use super::*;
+use benchmark_helpers::*;
+use frame_benchmarking::{account, benchmarks_instance_pallet, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+benchmarks_instance_pallet! {
+	transfer_non_native_currency {
+		let currency_id = non_native_currency::<T, I>();
+		let from: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, SEED);
+		let amount = fund::<T, I>(currency_id, &from)?;
+		set_max_locks::<T, I>(currency_id, &from, One::one(), T::MaxLocks::get())?;
+		let half = amount / 2u32.into();
+	}: transfer(RawOrigin::Signed(from), T::Lookup::unlookup(to.clone()), currency_id, half)
+	verify {
+		assert_eq!(<Pallet<T, I> as Stp258Currency<T::AccountId>>::free_balance(currency_id, &to), half);
+	}
+
+	transfer_native_currency {
+		let currency_id = <Pallet<T, I>>::native_currency_id();
+		let from: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, SEED);
+		let amount = fund::<T, I>(currency_id, &from)?;
+		set_max_locks::<T, I>(currency_id, &from, One::one(), T::MaxLocks::get())?;
+		let half = amount / 2u32.into();
+	}: _(RawOrigin::Signed(from), T::Lookup::unlookup(to.clone()), half)
+	verify {
+		assert_eq!(<Pallet<T, I> as Stp258Currency<T::AccountId>>::free_balance(currency_id, &to), half);
+	}
+
+	update_balance_non_native_currency {
+		let currency_id = non_native_currency::<T, I>();
+		let who: T::AccountId = account("who", 0, SEED);
+		let amount = fund::<T, I>(currency_id, &who)?;
+	}: update_balance(T::MonetaryOrigin::successful_origin(), T::Lookup::unlookup(who.clone()), currency_id, One::one())
+	verify {
+		assert_eq!(
+			<Pallet<T, I> as Stp258Currency<T::AccountId>>::free_balance(currency_id, &who),
+			amount.saturating_add(One::one())
+		);
+	}
+
+	announce_update {
+		let currency_id = non_native_currency::<T, I>();
+		let who: T::AccountId = account("who", 0, SEED);
+		let id = <Pallet<T, I>>::next_update_id();
+	}: _(T::MonetaryOrigin::successful_origin(), T::Lookup::unlookup(who), currency_id, One::one())
+	verify {
+		assert!(<Pallet<T, I>>::pending_updates(id).is_some());
+	}
+
+	execute_update {
+		let currency_id = non_native_currency::<T, I>();
+		let who: T::AccountId = account("who", 0, SEED);
+		fund::<T, I>(currency_id, &who)?;
+		let id = <Pallet<T, I>>::next_update_id();
+		fill_pending_updates::<T, I>(currency_id, &who, One::one(), 1)?;
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert!(<Pallet<T, I>>::pending_updates(id).is_none());
+	}
+
+	register_lock_prefix {
+	}: _(T::ComplianceOrigin::successful_origin(), BENCHMARK_LOCK_PREFIX)
+	verify {
+		assert!(<Pallet<T, I>>::lock_prefix_registered(BENCHMARK_LOCK_PREFIX));
+	}
+
+	deregister_lock_prefix {
+		register_benchmark_lock_prefix::<T, I>();
+	}: _(T::ComplianceOrigin::successful_origin(), BENCHMARK_LOCK_PREFIX)
+	verify {
+		assert!(!<Pallet<T, I>>::lock_prefix_registered(BENCHMARK_LOCK_PREFIX));
+	}
+
+	set_serp_parameter {
+		let parameter = SerpParameter::AdjustmentFrequency(10u32.into());
+	}: _(T::SerpOrigin::successful_origin(), parameter)
+	verify {
+		assert_eq!(<Pallet<T, I>>::serp_parameter(SerpParameterKey::AdjustmentFrequency), Some(parameter));
+	}
+
+	expire_locks {
+		let n in 0 .. T::MaxLockExpiriesPerBlock::get().min(T::MaxLocks::get());
+		let currency_id = non_native_currency::<T, I>();
+		let who: T::AccountId = account("who", 0, SEED);
+		fund::<T, I>(currency_id, &who)?;
+		let at = <frame_system::Module<T>>::block_number().saturating_add(One::one());
+		fill_lock_expiry_queue::<T, I>(currency_id, &who, One::one(), at, n)?;
+	}: {
+		<Pallet<T, I> as OnInitialize<T::BlockNumber>>::on_initialize(at);
+	}
+	verify {
+		assert!(<Pallet<T, I>>::lock_expiry_queue(at).is_empty());
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
+
+/// A regression harness checking the time the benchmarked calls take in
+/// the mock runtime against the weights `Config::WeightInfo` declares.
+#[cfg(test)]
+mod weight_regression {
+	use super::*;
+	use crate::mock::{ExtBuilder, MaxLockExpiriesPerBlock, MaxLocks, Runtime};
+	use frame_benchmarking::{BenchmarkParameter, BenchmarkingSetup};
+	use std::time::Instant;
+
+	/// How far the measured time of a call may exceed its declared weight.
+	const WEIGHT_MARGIN: Perbill = Perbill::from_percent(10);
+
+	/// Set up `benchmark` with `components` and assert that the benchmarked
+	/// call alone takes no longer than `declared`, plus `WEIGHT_MARGIN`.
+	fn assert_within_weight(
+		name: &str,
+		benchmark: SelectedBenchmark,
+		components: &[(BenchmarkParameter, u32)],
+		declared: Weight,
+	) {
+		ExtBuilder::default().build().execute_with(|| {
+			let call = <SelectedBenchmark as BenchmarkingSetup<Runtime>>::instance(&benchmark, components, false)
+				.expect("benchmark set up failed");
+			let start = Instant::now();
+			call().expect("benchmarked call failed");
+			// a unit of weight is a picosecond
+			let measured = start.elapsed().as_nanos().saturating_mul(1_000) as Weight;
+			assert!(
+				measured <= declared.saturating_add(WEIGHT_MARGIN * declared),
+				"{} took {} but declares {}",
+				name,
+				measured,
+				declared,
+			);
+		});
+	}
+
+	#[test]
+	fn benchmarked_calls_should_fit_their_weights() {
+		type Weights = <Runtime as Config>::WeightInfo;
+
+		assert_within_weight(
+			"transfer_non_native_currency",
+			SelectedBenchmark::transfer_non_native_currency,
+			&[],
+			Weights::transfer_non_native_currency(),
+		);
+		assert_within_weight(
+			"transfer_native_currency",
+			SelectedBenchmark::transfer_native_currency,
+			&[],
+			Weights::transfer_native_currency(),
+		);
+		assert_within_weight(
+			"update_balance_non_native_currency",
+			SelectedBenchmark::update_balance_non_native_currency,
+			&[],
+			Weights::update_balance_non_native_currency(),
+		);
+		assert_within_weight(
+			"announce_update",
+			SelectedBenchmark::announce_update,
+			&[],
+			Weights::announce_update(),
+		);
+		assert_within_weight(
+			"execute_update",
+			SelectedBenchmark::execute_update,
+			&[],
+			Weights::execute_update(),
+		);
+		assert_within_weight(
+			"register_lock_prefix",
+			SelectedBenchmark::register_lock_prefix,
+			&[],
+			Weights::register_lock_prefix(),
+		);
+		assert_within_weight(
+			"deregister_lock_prefix",
+			SelectedBenchmark::deregister_lock_prefix,
+			&[],
+			Weights::deregister_lock_prefix(),
+		);
+		assert_within_weight(
+			"set_serp_parameter",
+			SelectedBenchmark::set_serp_parameter,
+			&[],
+			Weights::set_serp_parameter(),
+		);
+		let n = MaxLockExpiriesPerBlock::get().min(MaxLocks::get());
+		assert_within_weight(
+			"expire_locks",
+			SelectedBenchmark::expire_locks,
+			&[(BenchmarkParameter::n, n)],
+			Weights::expire_locks(n),
+		);
+	}
+}
@@ -0,0 +1,72 @@
+//! Conversions between signed `Amount`s and unsigned `Balance`s.
+
+use serp_traits::arithmetic::{Signed, SimpleArithmetic};
+use sp_runtime::traits::{Bounded, CheckedAdd, CheckedSub, Zero};
+use sp_std::convert::{TryFrom, TryInto};
+
+/// Convert the magnitude of `amount` into a balance.
+///
+/// Returns `None` if the magnitude does not fit into `Balance`.
+pub fn amount_to_balance_checked<Amount, Balance>(amount: Amount) -> Option<Balance>
+where
+	Amount: Signed + SimpleArithmetic + TryInto<Balance>,
+{
+	let magnitude = if amount.is_negative() {
+		Amount::zero().checked_sub(&amount)?
+	} else {
+		amount
+	};
+	magnitude.try_into().ok()
+}
+
+/// Convert `balance` into a positive amount.
+///
+/// Returns `None` if `balance` does not fit into `Amount`.
+pub fn balance_to_amount_checked<Amount, Balance>(balance: Balance) -> Option<Amount>
+where
+	Amount: TryFrom<Balance>,
+{
+	Amount::try_from(balance).ok()
+}
+
+/// Convert `balance` into a positive amount, saturating at the maximum
+/// `Amount`.
+pub fn balance_to_amount_saturating<Amount, Balance>(balance: Balance) -> Amount
+where
+	Amount: TryFrom<Balance> + Bounded,
+{
+	balance_to_amount_checked(balance).unwrap_or_else(Amount::max_value)
+}
+
+/// The signed amount that takes a balance from `from` to `to`.
+///
+/// Returns `None` if the difference does not fit into `Amount`.
+pub fn balance_delta<Amount, Balance>(from: Balance, to: Balance) -> Option<Amount>
+where
+	Amount: Signed + SimpleArithmetic + TryFrom<Balance>,
+	Balance: SimpleArithmetic,
+{
+	if to >= from {
+		balance_to_amount_checked(to - from)
+	} else {
+		Amount::zero().checked_sub(&balance_to_amount_checked(from - to)?)
+	}
+}
+
+/// Apply the signed `amount` to `balance`.
+///
+/// Returns `None` on overflow, underflow or if `amount` does not fit into
+/// `Balance`.
+pub fn apply_amount<Amount, Balance>(balance: Balance, amount: Amount) -> Option<Balance>
+where
+	Amount: Signed + SimpleArithmetic + TryInto<Balance>,
+	Balance: SimpleArithmetic,
+{
+	let is_negative = amount.is_negative();
+	let by_balance: Balance = amount_to_balance_checked(amount)?;
+	if is_negative {
+		balance.checked_sub(&by_balance)
+	} else {
+		balance.checked_add(&by_balance)
+	}
+}
@@ -1,4 +1,10 @@
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! Weights for the module, originally generated with the Substrate
+//! benchmark CLI version 2.0.0.
+//!
+//! The weights of calls added since, and the extra reads and writes of
+//! transfer guards, demurrage, volume tracking, checkpoints, operation nonces
+//! and deposit account sweeps, were estimated by hand and are not benchmark
+//! output. Regenerate before relying on them in production.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -9,11 +15,18 @@ use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 impl crate::WeightInfo for () {
 	fn transfer_non_native_currency() -> Weight {
 		(172_011_000 as Weight)
-			.saturating_add(DbWeight::get().reads(5 as Weight))
-			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().reads(18 as Weight))
+			.saturating_add(DbWeight::get().writes(8 as Weight))
 	}
 	fn transfer_native_currency() -> Weight {
 		(43_023_000 as Weight)
+			.saturating_add(DbWeight::get().reads(13 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn transfer_all_keep_alive() -> Weight {
+		(181_377_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
 	}
 	fn update_balance_non_native_currency() -> Weight {
 		(137_440_000 as Weight)
@@ -26,4 +39,199 @@ impl crate::WeightInfo for () {
 	fn update_balance_native_currency_killing() -> Weight {
 		(62_595_000 as Weight)
 	}
+	fn announce_update() -> Weight {
+		(31_204_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn execute_update() -> Weight {
+		(158_730_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn veto_update() -> Weight {
+		(24_117_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_slash_destination() -> Weight {
+		(19_862_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn register_lock_prefix() -> Weight {
+		(21_409_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn deregister_lock_prefix() -> Weight {
+		(20_976_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn expire_locks(n: u32) -> Weight {
+		(6_291_000 as Weight)
+			.saturating_add((48_305_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_native_currency_id() -> Weight {
+		(18_540_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_serp_parameter() -> Weight {
+		(17_318_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn governed_adjust_supply() -> Weight {
+		(146_882_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn issue_native(n: u32) -> Weight {
+		(24_118_000 as Weight)
+			.saturating_add((57_460_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_issuance_schedule() -> Weight {
+		(21_742_000 as Weight)
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_demurrage_rate() -> Weight {
+		(19_204_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_savings_rate() -> Weight {
+		(23_915_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn deposit_to_savings() -> Weight {
+		(84_502_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn withdraw_from_savings() -> Weight {
+		(163_377_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn set_volume_tracking() -> Weight {
+		(17_093_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_fee_split() -> Weight {
+		(16_488_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn snapshot_currency() -> Weight {
+		(41_307_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn restore_currency() -> Weight {
+		(38_164_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn expire_reserves(n: u32) -> Weight {
+		(5_874_000 as Weight)
+			.saturating_add((39_512_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_slash_order() -> Weight {
+		(17_862_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn add_delegate() -> Weight {
+		(24_617_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_delegate() -> Weight {
+		(22_940_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn delegated_transfer() -> Weight {
+		(186_352_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_transfer_guard() -> Weight {
+		(23_805_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_transfer_guard() -> Weight {
+		(23_117_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn confirm_transfer() -> Weight {
+		(181_446_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn transfer_with_authorization() -> Weight {
+		(224_631_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn set_currency_metadata() -> Weight {
+		(21_948_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn enable_checkpointing() -> Weight {
+		(20_362_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn sample_issuance(n: u32) -> Weight {
+		(3_216_000 as Weight)
+			.saturating_add((9_874_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn redistribute_dust(n: u32) -> Weight {
+		(2_975_000 as Weight)
+			.saturating_add((72_418_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn derive_deposit_account() -> Weight {
+		(27_531_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn sweep_deposit_account() -> Weight {
+		(183_904_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn prune_fee_revenue() -> Weight {
+		(4_108_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn enable_child_trie_checkpointing() -> Weight {
+		(21_870_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn prune_checkpoints(n: u32) -> Weight {
+		(15_402_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
 }
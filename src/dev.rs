@@ -0,0 +1,63 @@
+//! Helper calls for local testing of SERP behaviour, compiled only with the
+//! `dev` feature.
+//!
+//! A dev runtime adds the module next to the Stp258Standard instance it
+//! serves, e.g. `Stp258Dev: stp258_standard::dev::{Module, Call, Storage}`,
+//! and wires `Pallet::price` into its price feed. Any signed account may
+//! call it, so never enable the `dev` feature for a production runtime.
+
+use super::*;
+
+pub use module::*;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config + crate::Config<I> {}
+
+	/// The prices set through `dev_set_price`, by currency.
+	#[pallet::storage]
+	#[pallet::getter(fn price)]
+	pub type Prices<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, FixedU128, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<T::BlockNumber> for Pallet<T, I> {}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Mint `amount` of `currency_id` into the caller.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn dev_mint_to_self(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			#[pallet::compact] amount: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			<crate::Pallet<T, I> as Stp258Currency<T::AccountId>>::deposit(currency_id, &who, amount)?;
+			Ok(().into())
+		}
+
+		/// Set the price of `currency_id`, or clear it with `None`.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn dev_set_price(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			price: Option<FixedU128>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Prices::<T, I>::mutate_exists(currency_id, |stored| *stored = price);
+			Ok(().into())
+		}
+	}
+}
@@ -0,0 +1,47 @@
+//! ERC-20 shaped views of the currencies, backing `Erc20CompatApi`.
+//!
+//! Allowances map to delegations: what a delegate may still transfer of the
+//! owner's balance in the current era.
+
+use super::*;
+
+/// The name of `currency_id`, if set.
+pub fn name<T: Config<I>, I: 'static>(currency_id: CurrencyIdOf<T, I>) -> Option<Vec<u8>> {
+	<Pallet<T, I>>::currency_metadata(currency_id).map(|metadata| metadata.name)
+}
+
+/// The symbol of `currency_id`, if set.
+pub fn symbol<T: Config<I>, I: 'static>(currency_id: CurrencyIdOf<T, I>) -> Option<Vec<u8>> {
+	<Pallet<T, I>>::currency_metadata(currency_id).map(|metadata| metadata.symbol)
+}
+
+/// The decimals of `currency_id`, if its base unit is a power of ten.
+pub fn decimals<T: Config<I>, I: 'static>(currency_id: CurrencyIdOf<T, I>) -> Option<u32> {
+	units::decimals::<T, I>(currency_id)
+}
+
+/// The total issuance of `currency_id`.
+pub fn total_supply<T: Config<I>, I: 'static>(currency_id: CurrencyIdOf<T, I>) -> BalanceOf<T, I> {
+	<Pallet<T, I> as Stp258Currency<T::AccountId>>::total_issuance(currency_id)
+}
+
+/// The free balance of `who` under `currency_id`.
+pub fn balance_of<T: Config<I>, I: 'static>(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> BalanceOf<T, I> {
+	<Pallet<T, I> as Stp258Currency<T::AccountId>>::free_balance(currency_id, who)
+}
+
+/// What `spender` may still transfer of `owner`'s `currency_id` in the
+/// current era.
+pub fn allowance<T: Config<I>, I: 'static>(
+	currency_id: CurrencyIdOf<T, I>,
+	owner: &T::AccountId,
+	spender: &T::AccountId,
+) -> BalanceOf<T, I> {
+	match <Pallet<T, I>>::delegation(owner, (currency_id, spender.clone())) {
+		Some(delegation) if delegation.era == <Pallet<T, I>>::era_of(<frame_system::Module<T>>::block_number()) => {
+			delegation.max_per_period.saturating_sub(delegation.spent)
+		}
+		Some(delegation) => delegation.max_per_period,
+		None => Zero::zero(),
+	}
+}
@@ -1,11 +1,17 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use frame_support::{
 	pallet_prelude::*,
+	storage::{
+		child::{self, ChildInfo},
+		StoragePrefixedMap,
+	},
 	traits::{
-		Currency as SetheumCurrency, ExistenceRequirement, Get, 
+		Currency as SetheumCurrency, EnsureOrigin, ExistenceRequirement, Get, Imbalance,
 		LockableCurrency as SetheumLockableCurrency,
 		ReservableCurrency as SetheumReservableCurrency, WithdrawReasons,
 	},
@@ -14,153 +20,3591 @@ use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
 use serp_traits::{
 	account::MergeAccount,
 	arithmetic::{Signed, SimpleArithmetic},
-	BalanceStatus, Stp258Asset, Stp258AssetExtended, Stp258AssetLockable, Stp258AssetReservable,
+	BalanceStatus, OnDust, Stp258Asset, Stp258AssetExtended, Stp258AssetLockable, Stp258AssetReservable,
 	LockIdentifier, Stp258Currency, Stp258CurrencyExtended, Stp258CurrencyReservable, Stp258CurrencyLockable,
 };
 use orml_utilities::with_transaction_result;
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, StaticLookup, Zero},
-	DispatchError, DispatchResult,
+	traits::{
+		AccountIdConversion, CheckedMul, CheckedSub, Hash, IdentifyAccount, MaybeSerializeDeserialize, One, SaturatedConversion, Saturating,
+		StaticLookup, Verify, Zero,
+	},
+	DispatchError, DispatchResult, FixedPointNumber, FixedU128, ModuleId, Perbill, RuntimeDebug,
 };
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
 	marker, result,
+	vec::Vec,
 };
 
-mod default_weight;
-mod mock;
-mod tests;
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub mod benchmark_helpers;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod conversions;
+mod default_weight;
+#[cfg(feature = "dev")]
+pub mod dev;
+pub mod erc20_compat;
+pub mod lock_id;
+pub mod migrations;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod mock;
+pub mod runtime_api;
+pub mod serp_parameters;
+#[cfg(feature = "std")]
+pub mod ss58;
+#[cfg(feature = "std")]
+pub mod state_dump;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod strategies;
+mod tests;
+pub mod units;
+
+pub use lock_id::LockPrefix;
+pub use module::*;
+pub use serp_parameters::{SerpParameter, SerpParameterKey};
+
+/// The log target of this module.
+pub(crate) const LOG_TARGET: &str = "runtime::stp258";
+
+/// The lock enforcing freezes on the backing currencies.
+pub const FREEZE_LOCK_ID: LockIdentifier = *b"stp/frz ";
+
+/// The prefix of offchain-indexed transfer records.
+pub const TRANSFER_INDEX_PREFIX: &[u8] = b"stp258::transfer";
+
+/// The context transfer authorizations are signed under, so the signature
+/// cannot be replayed as any other signed message.
+///
+/// The genesis hash and the instance's `Config::ModuleId` are signed too,
+/// binding the signature to one chain and one instance of this pallet.
+pub const AUTHORIZATION_CONTEXT: &[u8] = b"stp258::authorization";
+
+/// Identifier of an announced balance update.
+pub type UpdateId = u32;
+
+/// A privileged balance update waiting for its delay to pass.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PendingUpdate<AccountId, CurrencyId, Amount, BlockNumber> {
+	/// The account whose balance will be updated.
+	pub who: AccountId,
+	/// The currency to update.
+	pub currency_id: CurrencyId,
+	/// The signed amount to update the balance by.
+	pub amount: Amount,
+	/// The block from which the update may be executed.
+	pub unlock_at: BlockNumber,
+}
+
+/// A transfer above its sender's guard threshold, waiting for the guardian
+/// to confirm it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PendingTransfer<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The sender.
+	pub from: AccountId,
+	/// The recipient.
+	pub to: AccountId,
+	/// The guardian of `from` when the transfer was queued, who alone may
+	/// confirm it.
+	pub guardian: AccountId,
+	/// The currency transferred.
+	pub currency_id: CurrencyId,
+	/// The amount transferred.
+	pub amount: Balance,
+	/// The last block the transfer may be confirmed in.
+	pub expires_at: BlockNumber,
+}
+
+/// A transfer signed offline by the payer, submitted by anyone through
+/// `transfer_with_authorization`.
+///
+/// The payer signs `(AUTHORIZATION_CONTEXT, genesis_hash, module_id,
+/// authorization).encode()`, see `Pallet::authorization_payload`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TransferAuthorization<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The payer.
+	pub from: AccountId,
+	/// The recipient.
+	pub dest: AccountId,
+	/// The currency transferred.
+	pub currency_id: CurrencyId,
+	/// The amount transferred.
+	pub amount: Balance,
+	/// The payer's authorization nonce, must equal `AuthorizationNonces`.
+	pub nonce: u64,
+	/// The last block the authorization may be submitted in.
+	pub deadline: BlockNumber,
+}
+
+impl<AccountId: Encode, CurrencyId: Encode, Balance: Encode, BlockNumber: Encode>
+	TransferAuthorization<AccountId, CurrencyId, Balance, BlockNumber>
+{
+	/// The message the payer signs for the chain with `genesis_hash` and
+	/// the pallet instance with `module_id`.
+	pub fn signing_payload<Hash: Encode>(&self, genesis_hash: &Hash, module_id: &ModuleId) -> Vec<u8> {
+		(AUTHORIZATION_CONTEXT, genesis_hash, module_id, self).encode()
+	}
+}
+
+/// Where the funds removed by a slash end up.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SlashDestination<AccountId> {
+	/// Slashed funds are burned.
+	Burn,
+	/// Slashed funds are credited to `Config::TreasuryAccount`.
+	Treasury,
+	/// Slashed funds are credited to the given account.
+	Account(AccountId),
+	/// The given portion of slashed funds is credited to
+	/// `Config::TreasuryAccount`, the rest is burned.
+	Split(Perbill),
+}
+
+impl<AccountId> Default for SlashDestination<AccountId> {
+	fn default() -> Self {
+		SlashDestination::Burn
+	}
+}
+
+/// What happens to reserved balance released to an account it would not
+/// bring up to the existential deposit, where the backing currency would
+/// otherwise dust it.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ReleaseFallback {
+	/// Credit `Config::TreasuryAccount` instead.
+	Treasury,
+	/// Leave the balance reserved.
+	KeepReserved,
+	/// Move the balance to `Config::TreasuryAccount`, where fee revenue is
+	/// collected, and credit the account from there with the balance
+	/// topped up to the existential deposit.
+	TopUp,
+}
+
+/// Derive the account of `sub` under `module_id`, for any account length.
+///
+/// Accounts of 32 bytes or more are `module_id.into_sub_account(sub)`:
+/// `b"modl"`, the module id and the SCALE encoding of `sub`, zero-padded or
+/// cut to the account length. Past `b"modl"` and the module id, shorter
+/// accounts such as the 20-byte accounts of EVM-compatible runtimes keep
+/// only a few bytes of `sub`. So unless the whole encoding fits, they are
+/// the leading bytes of its `Hashing` hash instead.
+pub fn derive_sub_account<AccountId, Hashing>(module_id: ModuleId, sub: impl Encode) -> AccountId
+where
+	AccountId: Encode + Decode + Default,
+	Hashing: Hash,
+{
+	let account_length = AccountId::default().encoded_size();
+	let encoded = (*b"modl", module_id, &sub).encode();
+	if account_length >= 32 || encoded.len() <= account_length {
+		return module_id.into_sub_account(sub);
+	}
+	AccountId::decode(&mut Hashing::hash(&encoded).as_ref()).unwrap_or_default()
+}
+
+/// A protocol account derived from `Config::ModuleId`.
+///
+/// The account of `sub_account` is
+/// `derive_sub_account(ModuleId, sub_account)`, which for 32-byte accounts
+/// is `ModuleId::into_sub_account(sub_account)`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SubAccount {
+	/// The protocol treasury.
+	Treasury,
+	/// The pot SERP operations are funded from.
+	SerpPot,
+	/// The escrow pot with the given id.
+	EscrowPot(u32),
+	/// The auction pot with the given id.
+	AuctionPot(u32),
+}
+
+/// A deposit account derived for `parent` with `derive_deposit_account`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DepositAccount<AccountId> {
+	/// The account controlling the deposit account.
+	pub parent: AccountId,
+	/// The tag the account was derived with.
+	pub tag: u32,
+	/// Whether non-native currency credited through this pallet is
+	/// forwarded to `parent` right away.
+	pub auto_sweep: bool,
+}
+
+/// Where the dust aggregated in a currency's dust account goes at the end
+/// of each era.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DustDestination {
+	/// Credit `Config::TreasuryAccount`.
+	Treasury,
+	/// Burn it.
+	Burn,
+}
+
+/// The order `slash` draws a currency from an account's balances in.
+///
+/// Reserved balance held for a reason through `HoldableCurrency` is never
+/// slashed.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SlashOrder {
+	/// Free balance first, then reserved balance.
+	FreeFirst,
+	/// Reserved balance first, then free balance.
+	ReservedFirst,
+	/// Free and reserved balance in proportion to their size.
+	Proportional,
+}
+
+impl Default for SlashOrder {
+	fn default() -> Self {
+		SlashOrder::FreeFirst
+	}
+}
+
+/// Storage releases of the pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Releases {
+	/// The original storage layout.
+	V1,
+	/// Adds the lock and reserve aggregates and the lock prefix registry.
+	V2,
+	/// Stores balances and amounts re-encoded by `migrations::WidenBalance`.
+	V3,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
+}
+
+/// What caused a recorded supply change.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SupplyChangeSource<Hash> {
+	/// A governance decision, with the hash of its rationale.
+	Governance(Hash),
+	/// The native issuance schedule.
+	Issuance,
+}
+
+/// How much native currency is issued every era.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum IssuanceSchedule<Balance> {
+	/// The same amount every era.
+	Fixed(Balance),
+	/// `initial` in the first era, reduced by `decay` every following era.
+	Decaying { initial: Balance, decay: Perbill },
+}
+
+impl<Balance: Copy> IssuanceSchedule<Balance> {
+	/// The amount issued in the first era of the schedule.
+	pub fn initial(&self) -> Balance {
+		match self {
+			IssuanceSchedule::Fixed(amount) => *amount,
+			IssuanceSchedule::Decaying { initial, .. } => *initial,
+		}
+	}
+}
+
+/// A change of a currency's supply.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SupplyChange<Amount, BlockNumber, Hash> {
+	/// The block the change happened in.
+	pub block: BlockNumber,
+	/// The signed change of supply.
+	pub amount: Amount,
+	/// What caused the change.
+	pub source: SupplyChangeSource<Hash>,
+}
+
+/// How fee revenue is shared out.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FeeSplit {
+	/// The share credited to `Config::TreasuryAccount`.
+	pub treasury: Perbill,
+	/// The share burned.
+	pub burn: Perbill,
+	/// The share credited to `Config::SerperAccount`.
+	pub serpers: Perbill,
+}
+
+impl FeeSplit {
+	/// Whether the shares add up to the whole.
+	pub fn is_valid(&self) -> bool {
+		self.treasury.deconstruct() as u64 + self.burn.deconstruct() as u64 + self.serpers.deconstruct() as u64
+			== Perbill::one().deconstruct() as u64
+	}
+}
+
+impl Default for FeeSplit {
+	fn default() -> Self {
+		FeeSplit {
+			treasury: Perbill::one(),
+			burn: Perbill::zero(),
+			serpers: Perbill::zero(),
+		}
+	}
+}
+
+/// An account's savings in a currency.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SavingsBalance<Balance> {
+	/// The account's shares of the currency's savings index.
+	pub shares: Balance,
+	/// The reserved amount backing the savings, interest excluded.
+	pub principal: Balance,
+}
+
+/// A summary of one currency's monetary state, stored under its hash by
+/// `snapshot_currency`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CurrencySnapshot<CurrencyId, Balance, AccountId> {
+	/// The currency summarised.
+	pub currency_id: CurrencyId,
+	/// Its total issuance, for reference only.
+	pub total_issuance: Balance,
+	/// The treasury's balance of it, for reference only.
+	pub treasury_balance: Balance,
+	/// Where its slashed funds are sent.
+	pub slash_destination: SlashDestination<AccountId>,
+	/// Its demurrage rate per block.
+	pub demurrage_rate: Option<Perbill>,
+	/// Its savings rate per block.
+	pub savings_rate: Perbill,
+	/// Whether its transfer volume is recorded.
+	pub volume_tracked: bool,
+}
+
+/// The display metadata of a currency.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CurrencyMetadata {
+	/// The currency's name, e.g. `"Setheum Dollar"`.
+	pub name: Vec<u8>,
+	/// The currency's ticker symbol, e.g. `"JUSD"`.
+	pub symbol: Vec<u8>,
+}
+
+/// A budget within which a delegate may transfer an account's balance of a
+/// currency.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Delegation<Balance> {
+	/// The most the delegate may transfer per era.
+	pub max_per_period: Balance,
+	/// The era `spent` was recorded in.
+	pub era: u32,
+	/// The amount transferred by the delegate in `era`.
+	pub spent: Balance,
+}
+
+/// Auxiliary per-account state of a currency, kept in one entry so a light
+/// client proves it with a single storage proof.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccountActivity<BlockNumber, Balance> {
+	/// The block demurrage was last charged at.
+	pub demurrage_charged_at: Option<BlockNumber>,
+	/// The era and amount last recorded as transferred. Volumes recorded in
+	/// earlier eras read as zero.
+	pub transfer_volume: Option<(u32, Balance)>,
+}
+
+/// A lock set on an account's balance through this pallet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BalanceLock<Balance> {
+	/// The lock identifier.
+	pub id: LockIdentifier,
+	/// The amount locked.
+	pub amount: Balance,
+}
+
+/// The consequence of withdrawing an amount from an account.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum WithdrawConsequence<Balance> {
+	/// The amount exceeds the total issuance of the currency.
+	Underflow,
+	/// The free balance is lower than the amount.
+	NoFunds,
+	/// The amount is held back by locks.
+	Frozen,
+	/// The withdrawal would take the account below the existential
+	/// deposit and reap it.
+	WouldDie,
+	/// The withdrawal can succeed, leaving the given free balance.
+	Success(Balance),
+}
+
+/// An internal decision of this pallet, reported as `Event::Traced` when
+/// built with the `trace-events` feature.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Trace<Balance> {
+	/// The call was routed to `Config::Stp258Native`.
+	RoutedNative,
+	/// The call was routed to `Config::Stp258Currency`.
+	RoutedNonNative,
+	/// An amount was clamped from `requested` down to `applied`.
+	Clamped { requested: Balance, applied: Balance },
+}
+
+/// Read access to the per-account state kept by this pallet, for
+/// downstream pallets.
+pub trait InspectAccounts<AccountId> {
+	type CurrencyId;
+	type Balance;
+	type PendingUpdate;
+
+	/// The free balance of `who` under `currency_id` that is not restricted
+	/// by locks set through this pallet.
+	fn usable_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
+
+	/// All announced balance updates, by id.
+	fn all_pending_updates() -> Vec<(UpdateId, Self::PendingUpdate)>;
+
+	/// Announced balance updates targeting `who`, by id.
+	fn pending_updates_of(who: &AccountId) -> Vec<(UpdateId, Self::PendingUpdate)>;
+
+	/// All currencies with a slash destination other than the default, and
+	/// their destination.
+	fn slash_destinations() -> Vec<(Self::CurrencyId, SlashDestination<AccountId>)>;
+}
+
+/// Reserves held for a typed reason, released only for the same reason, so
+/// features sharing an account's reserve cannot release each other's
+/// funds.
+pub trait HoldableCurrency<AccountId> {
+	type CurrencyId;
+	type Balance;
+	type Reason;
+
+	/// The balance of `currency_id` held on `who` for `reason`.
+	fn balance_on_hold(reason: Self::Reason, currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
+
+	/// The balance of `currency_id` held on `who` for any reason.
+	fn total_balance_on_hold(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
+
+	/// Reserve `amount` of `currency_id` on `who`, held for `reason`.
+	fn hold(reason: Self::Reason, currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+	/// Release up to `amount` of `currency_id` held on `who` for `reason`,
+	/// returning the amount unreserved.
+	fn release(
+		reason: Self::Reason,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> Self::Balance;
+}
+
+/// Per-era transfer volume recorded by this pallet, for liquidity mining
+/// and rewards pallets.
+pub trait TransferVolume<AccountId> {
+	type CurrencyId;
+	type Balance;
+
+	/// The index of the current era, `Config::EraLength` blocks long.
+	fn current_era() -> u32;
+
+	/// The amount of `currency_id` transferred by `who` in the current era,
+	/// zero if `currency_id` is not tracked.
+	fn transfer_volume(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
+}
+
+/// Activity counters reported by this pallet when built with the `metrics`
+/// feature, for the node to export to Prometheus.
+pub trait Metrics<CurrencyId> {
+	/// A transfer of `currency_id` was made.
+	fn on_transfer(currency_id: CurrencyId);
+
+	/// The supply of `currency_id` was adjusted by governance.
+	fn on_serp_action(currency_id: CurrencyId);
+
+	/// A withdrawal of `currency_id` failed.
+	fn on_failed_withdrawal(currency_id: CurrencyId);
+}
+
+impl<CurrencyId> Metrics<CurrencyId> for () {
+	fn on_transfer(_: CurrencyId) {}
+	fn on_serp_action(_: CurrencyId) {}
+	fn on_failed_withdrawal(_: CurrencyId) {}
+}
+
+/// Observes deposits made through this pallet.
+pub trait OnDeposit<AccountId, CurrencyId, Balance> {
+	/// `amount` of `currency_id` was deposited into `who`.
+	fn on_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance);
+}
+
+impl<AccountId, CurrencyId, Balance> OnDeposit<AccountId, CurrencyId, Balance> for () {
+	fn on_deposit(_: CurrencyId, _: &AccountId, _: Balance) {}
+}
+
+/// Observes withdrawals made through this pallet.
+pub trait OnWithdraw<AccountId, CurrencyId, Balance> {
+	/// `amount` of `currency_id` was withdrawn from `who`.
+	fn on_withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance);
+}
+
+impl<AccountId, CurrencyId, Balance> OnWithdraw<AccountId, CurrencyId, Balance> for () {
+	fn on_withdraw(_: CurrencyId, _: &AccountId, _: Balance) {}
+}
+
+/// Observes slashes made through this pallet, free or reserved.
+pub trait OnSlash<AccountId, CurrencyId, Balance> {
+	/// `amount` of `currency_id` was slashed from `who`.
+	fn on_slash(currency_id: CurrencyId, who: &AccountId, amount: Balance);
+}
+
+impl<AccountId, CurrencyId, Balance> OnSlash<AccountId, CurrencyId, Balance> for () {
+	fn on_slash(_: CurrencyId, _: &AccountId, _: Balance) {}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub trait WeightInfo {
+		fn transfer_non_native_currency() -> Weight;
+		fn transfer_native_currency() -> Weight;
+		fn transfer_all_keep_alive() -> Weight;
+		fn update_balance_non_native_currency() -> Weight;
+		fn update_balance_native_currency_creating() -> Weight;
+		fn update_balance_native_currency_killing() -> Weight;
+		fn announce_update() -> Weight;
+		fn execute_update() -> Weight;
+		fn veto_update() -> Weight;
+		fn set_slash_destination() -> Weight;
+		fn register_lock_prefix() -> Weight;
+		fn deregister_lock_prefix() -> Weight;
+		fn expire_locks(n: u32) -> Weight;
+		fn set_native_currency_id() -> Weight;
+		fn set_serp_parameter() -> Weight;
+		fn governed_adjust_supply() -> Weight;
+		fn issue_native(n: u32) -> Weight;
+		fn set_issuance_schedule() -> Weight;
+		fn set_demurrage_rate() -> Weight;
+		fn set_savings_rate() -> Weight;
+		fn deposit_to_savings() -> Weight;
+		fn withdraw_from_savings() -> Weight;
+		fn set_volume_tracking() -> Weight;
+		fn set_fee_split() -> Weight;
+		fn snapshot_currency() -> Weight;
+		fn restore_currency() -> Weight;
+		fn expire_reserves(n: u32) -> Weight;
+		fn set_slash_order() -> Weight;
+		fn add_delegate() -> Weight;
+		fn remove_delegate() -> Weight;
+		fn delegated_transfer() -> Weight;
+		fn set_transfer_guard() -> Weight;
+		fn remove_transfer_guard() -> Weight;
+		fn confirm_transfer() -> Weight;
+		fn transfer_with_authorization() -> Weight;
+		fn set_currency_metadata() -> Weight;
+		fn enable_checkpointing() -> Weight;
+		fn sample_issuance(n: u32) -> Weight;
+		fn redistribute_dust(n: u32) -> Weight;
+		fn derive_deposit_account() -> Weight;
+		fn sweep_deposit_account() -> Weight;
+		fn prune_fee_revenue() -> Weight;
+		fn enable_child_trie_checkpointing() -> Weight;
+		fn prune_checkpoints(n: u32) -> Weight;
+	}
+
+	pub(crate) type BalanceOf<T, I = ()> =
+		<<T as Config<I>>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T, I = ()> =
+		<<T as Config<I>>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub(crate) type AmountOf<T, I = ()> =
+		<<T as Config<I>>::Stp258Currency as Stp258CurrencyExtended<<T as frame_system::Config>::AccountId>>::Amount;
+	pub(crate) type SupplyChangeOf<T, I = ()> = SupplyChange<
+		AmountOf<T, I>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as frame_system::Config>::Hash,
+	>;
+	pub(crate) type TransferAuthorizationOf<T, I = ()> = TransferAuthorization<
+		<T as frame_system::Config>::AccountId,
+		CurrencyIdOf<T, I>,
+		BalanceOf<T, I>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub(crate) type PendingTransferOf<T, I = ()> = PendingTransfer<
+		<T as frame_system::Config>::AccountId,
+		CurrencyIdOf<T, I>,
+		BalanceOf<T, I>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub(crate) type CurrencySnapshotOf<T, I = ()> =
+		CurrencySnapshot<CurrencyIdOf<T, I>, BalanceOf<T, I>, <T as frame_system::Config>::AccountId>;
+	pub(crate) type SerpParameterOf<T, I = ()> = SerpParameter<<T as frame_system::Config>::BlockNumber, BalanceOf<T, I>>;
+	pub(crate) type PendingUpdateOf<T, I = ()> = PendingUpdate<
+		<T as frame_system::Config>::AccountId,
+		CurrencyIdOf<T, I>,
+		AmountOf<T, I>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+
+		type Stp258Currency: MergeAccount<Self::AccountId>
+			+ Stp258CurrencyExtended<Self::AccountId>
+			+ Stp258CurrencyLockable<Self::AccountId>
+			+ Stp258CurrencyReservable<Self::AccountId>;
+
+		type Stp258Native: Stp258AssetExtended<Self::AccountId, Balance = BalanceOf<Self, I>, Amount = AmountOf<Self, I>>
+			+ Stp258AssetLockable<Self::AccountId, Balance = BalanceOf<Self, I>>
+			+ Stp258AssetReservable<Self::AccountId, Balance = BalanceOf<Self, I>>;
+
+		#[pallet::constant]
+		type GetStp258NativeId: Get<CurrencyIdOf<Self, I>>;
+
+		/// The origin which may change balances and monetary policy:
+		/// `update_balance`, announced updates and slash destinations.
+		type MonetaryOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may manage restrictions on balances, such as the
+		/// lock prefix registry.
+		type ComplianceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may veto announced balance updates.
+		type EmergencyOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may set SERP parameters.
+		type SerpOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The referendum-backed origin which may adjust supply at its
+		/// discretion.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of supply changes kept per currency.
+		#[pallet::constant]
+		type MaxSupplyHistory: Get<u32>;
+
+		/// The maximum number of locks that may expire in the same block.
+		#[pallet::constant]
+		type MaxLockExpiriesPerBlock: Get<u32>;
+
+		/// The maximum number of reserves that may be released in the same
+		/// block.
+		#[pallet::constant]
+		type MaxReserveExpiriesPerBlock: Get<u32>;
+
+		/// The maximum number of locks set through this pallet on an account,
+		/// per currency.
+		#[pallet::constant]
+		type MaxLocks: Get<u32>;
+
+		/// The maximum length of a currency's name or symbol.
+		#[pallet::constant]
+		type MaxMetadataLength: Get<u32>;
+
+		/// The maximum number of balance checkpoints kept per account and
+		/// currency, the oldest are dropped first.
+		#[pallet::constant]
+		type MaxCheckpoints: Get<u32>;
+
+		/// The currencies whose total issuance is sampled at era boundaries
+		/// and SERP adjustment points.
+		type SampledCurrencies: Get<Vec<CurrencyIdOf<Self, I>>>;
+
+		/// The maximum number of total issuance samples kept per currency,
+		/// the oldest are dropped first.
+		#[pallet::constant]
+		type MaxIssuanceSamples: Get<u32>;
+
+		/// The number of eras, the current one included, fee revenue is
+		/// kept for in `FeeRevenue`.
+		#[pallet::constant]
+		type FeeRevenueRetention: Get<u32>;
+
+		/// The maximum number of accounts `balances_of` answers for.
+		#[pallet::constant]
+		type MaxBalancesPerQuery: Get<u32>;
+
+		/// The maximum number of native issuance beneficiaries.
+		#[pallet::constant]
+		type MaxIssuanceBeneficiaries: Get<u32>;
+
+		/// The number of blocks in an era of the native issuance schedule.
+		#[pallet::constant]
+		type EraLength: Get<Self::BlockNumber>;
+
+		/// The number of blocks a guardian has to confirm a guarded
+		/// transfer before it lapses.
+		#[pallet::constant]
+		type TransferConfirmationWindow: Get<Self::BlockNumber>;
+
+		/// The number of blocks an announced balance update must wait
+		/// before it can be executed.
+		#[pallet::constant]
+		type UpdateDelay: Get<Self::BlockNumber>;
+
+		/// The account credited by the `Treasury` and `Split` slash
+		/// destinations.
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The account credited with the serpers' share of fee revenue.
+		type SerperAccount: Get<Self::AccountId>;
+
+		/// The id protocol sub-accounts are derived from.
+		#[pallet::constant]
+		type ModuleId: Get<ModuleId>;
+
+		/// The id the per-currency dust accounts are derived from.
+		#[pallet::constant]
+		type DustModuleId: Get<ModuleId>;
+
+		/// Where aggregated dust goes at the end of each era.
+		#[pallet::constant]
+		type DustDestination: Get<DustDestination>;
+
+		/// The maximum number of currencies whose dust is redistributed in
+		/// the same block.
+		#[pallet::constant]
+		type MaxDustRedistributionsPerBlock: Get<u32>;
+
+		/// What happens to reserved balance repatriated to an account below
+		/// the existential deposit.
+		#[pallet::constant]
+		type ReleaseFallback: Get<ReleaseFallback>;
+
+		/// The reasons balance is held for, usually a runtime-wide enum
+		/// composed from the pallets using `HoldableCurrency`.
+		type HoldReason: Parameter + Member + Copy + MaybeSerializeDeserialize;
+
+		/// The reason reserves scheduled through `reserve_until` are held
+		/// for.
+		#[pallet::constant]
+		type ReserveExpiryHoldReason: Get<Self::HoldReason>;
+
+		/// The signature payers sign transfer authorizations with.
+		type AuthorizationSignature: Parameter + Verify<Signer = Self::AuthorizationSigner>;
+
+		/// The public key checking `AuthorizationSignature`, identifying the
+		/// payer's account.
+		type AuthorizationSigner: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// Notified of every deposit.
+		type OnDeposit: OnDeposit<Self::AccountId, CurrencyIdOf<Self, I>, BalanceOf<Self, I>>;
+
+		/// Notified of every withdrawal.
+		type OnWithdraw: OnWithdraw<Self::AccountId, CurrencyIdOf<Self, I>, BalanceOf<Self, I>>;
+
+		/// Notified of every slash.
+		type OnSlash: OnSlash<Self::AccountId, CurrencyIdOf<Self, I>, BalanceOf<Self, I>>;
+
+		/// Receives activity counters when built with the `metrics` feature.
+		type Metrics: Metrics<<Self::Stp258Currency as Stp258Currency<Self::AccountId>>::CurrencyId>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Unable to convert the Amount type into Balance.
+		AmountIntoBalanceFailed,
+		/// Balance is too low.
+		BalanceTooLow,
+		/// No announced update exists for the given id.
+		UpdateNotFound,
+		/// The announced update is still within its delay.
+		UpdateNotUnlocked,
+		/// No update id is available.
+		NoAvailableUpdateId,
+		/// Beneficiary account must pre-exist.
+		DeadAccount,
+		/// The prefix of a namespaced lock identifier is not registered.
+		UnregisteredLockPrefix,
+		/// The lock prefix is already registered.
+		LockPrefixAlreadyRegistered,
+		/// The lock expiry block is not in the future.
+		InvalidLockExpiry,
+		/// The issuance shares add up to more than the whole.
+		InvalidIssuanceShares,
+		/// Too many locks already expire in the requested block.
+		TooManyLockExpiries,
+		/// The account already has `MaxLocks` locks in the currency.
+		TooManyLocks,
+		/// Too many reserves are already released in the requested block.
+		TooManyReserveExpiries,
+		/// A reserve release block must be in the future.
+		InvalidReserveExpiry,
+		/// The reserved balance is lower than the amount to repatriate.
+		InsufficientReservedBalance,
+		/// The caller is not a delegate of the account in the currency.
+		NotDelegate,
+		/// The transfer exceeds the delegate's budget for the era.
+		DelegationBudgetExceeded,
+		/// The account already has a transfer guard in the currency.
+		TransferGuardExists,
+		/// The account has no transfer guard in the currency.
+		NoTransferGuard,
+		/// The caller is not the guardian of the transfer.
+		NotGuardian,
+		/// No pending transfer with the given id.
+		UnknownPendingTransfer,
+		/// Pending transfer ids are exhausted.
+		NoAvailableTransferId,
+		/// The caller already derived a deposit account with the tag.
+		DepositAccountExists,
+		/// The account is not a deposit account of the caller.
+		NotDepositAccountOwner,
+		/// The sub-account is already registered.
+		SubAccountAlreadyRegistered,
+		/// The sub-account is not registered.
+		UnknownSubAccount,
+		/// Balance checkpointing is already enabled for the currency.
+		CheckpointingAlreadyEnabled,
+		/// The currency's checkpoints are not kept in a child trie.
+		CheckpointsNotInChildTrie,
+		/// The currency's checkpoint child trie is still being pruned.
+		CheckpointPruneInProgress,
+		/// A currency name or symbol longer than `MaxMetadataLength`.
+		MetadataTooLong,
+		/// The authorization is not signed by its payer.
+		InvalidAuthorizationSignature,
+		/// The authorization's deadline has passed.
+		AuthorizationExpired,
+		/// The authorization's nonce is not the payer's next nonce.
+		InvalidAuthorizationNonce,
+		/// More issuance beneficiaries than `MaxIssuanceBeneficiaries`.
+		TooManyIssuanceBeneficiaries,
+		/// The fee split shares do not add up to the whole.
+		InvalidFeeSplit,
+		/// The savings are lower than the amount to withdraw.
+		InsufficientSavings,
+		/// No currency snapshot is stored under the id.
+		UnknownSnapshot,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Currency transfer success, with the amount actually credited and
+		/// the operation nonces of both accounts.
+		/// [currency_id, from, to, amount, from_nonce, to_nonce]
+		Transferred(CurrencyIdOf<T, I>, T::AccountId, T::AccountId, BalanceOf<T, I>, u64, u64),
+		/// Update balance success. [currency_id, who, amount, nonce]
+		BalanceUpdated(CurrencyIdOf<T, I>, T::AccountId, AmountOf<T, I>, u64),
+		/// Deposit success. [currency_id, who, amount, nonce]
+		Deposited(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>, u64),
+		/// Withdraw success. [currency_id, who, amount, nonce]
+		Withdrawn(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>, u64),
+		/// Balance update announced. [update_id, currency_id, who, amount,
+		/// unlock_at]
+		UpdateAnnounced(UpdateId, CurrencyIdOf<T, I>, T::AccountId, AmountOf<T, I>, T::BlockNumber),
+		/// Announced balance update executed. [update_id]
+		UpdateExecuted(UpdateId),
+		/// Announced balance update vetoed. [update_id]
+		UpdateVetoed(UpdateId),
+		/// Slash destination set. [currency_id, destination]
+		SlashDestinationSet(CurrencyIdOf<T, I>, SlashDestination<T::AccountId>),
+		/// Funds slashed, `None` order for reserved balance slashes.
+		/// [currency_id, who, amount, destination, order, nonce]
+		Slashed(
+			CurrencyIdOf<T, I>,
+			T::AccountId,
+			BalanceOf<T, I>,
+			SlashDestination<T::AccountId>,
+			Option<SlashOrder>,
+			u64,
+		),
+		/// Slash order set. [currency_id, order]
+		SlashOrderSet(CurrencyIdOf<T, I>, SlashOrder),
+		/// Lock prefix registered. [prefix]
+		LockPrefixRegistered(LockPrefix),
+		/// Lock prefix deregistered. [prefix]
+		LockPrefixDeregistered(LockPrefix),
+		/// Lock expired and was removed. [currency_id, who, lock_id]
+		LockExpired(CurrencyIdOf<T, I>, T::AccountId, LockIdentifier),
+		/// The native currency id changed. [old_currency_id, new_currency_id]
+		NativeCurrencyIdMigrated(CurrencyIdOf<T, I>, CurrencyIdOf<T, I>),
+		/// SERP parameter set. [parameter]
+		SerpParameterSet(SerpParameterOf<T, I>),
+		/// Supply adjusted by governance. [currency_id, amount, rationale]
+		SupplyAdjusted(CurrencyIdOf<T, I>, AmountOf<T, I>, T::Hash),
+		/// Native issuance schedule set. [schedule]
+		IssuanceScheduleSet(Option<IssuanceSchedule<BalanceOf<T, I>>>),
+		/// Native currency issued for an era. [amount]
+		NativeIssued(BalanceOf<T, I>),
+		/// Demurrage rate set. [currency_id, rate_per_block]
+		DemurrageRateSet(CurrencyIdOf<T, I>, Option<Perbill>),
+		/// Demurrage charged. [currency_id, who, amount]
+		DemurrageCharged(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>),
+		/// Savings rate set. [currency_id, rate_per_block]
+		SavingsRateSet(CurrencyIdOf<T, I>, Perbill),
+		/// Deposited to savings. [currency_id, who, amount]
+		SavingsDeposited(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>),
+		/// Fee split set. [fee_split]
+		FeeSplitSet(FeeSplit),
+		/// Transfer volume tracking set. [currency_id, enabled]
+		VolumeTrackingSet(CurrencyIdOf<T, I>, bool),
+		/// Withdrawn from savings, interest included. [currency_id, who,
+		/// amount]
+		SavingsWithdrawn(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>),
+		/// Savings interest the treasury could not pay, left in savings.
+		/// [currency_id, who, amount]
+		SavingsInterestUnpaid(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>),
+		/// Internal decision, only emitted with the `trace-events` feature.
+		/// [currency_id, trace]
+		Traced(CurrencyIdOf<T, I>, Trace<BalanceOf<T, I>>),
+		/// Currency snapshot taken. [currency_id, snapshot_id]
+		CurrencySnapshotTaken(CurrencyIdOf<T, I>, T::Hash),
+		/// Currency parameters restored from a snapshot. [currency_id,
+		/// snapshot_id]
+		CurrencyRestored(CurrencyIdOf<T, I>, T::Hash),
+		/// Balance held. [currency_id, who, reason, amount]
+		Held(CurrencyIdOf<T, I>, T::AccountId, T::HoldReason, BalanceOf<T, I>),
+		/// Held balance released. [currency_id, who, reason, amount]
+		Released(CurrencyIdOf<T, I>, T::AccountId, T::HoldReason, BalanceOf<T, I>),
+		/// Reserved balance moved to another account's free balance.
+		/// [currency_id, who, beneficiary, amount]
+		UnreservedTo(CurrencyIdOf<T, I>, T::AccountId, T::AccountId, BalanceOf<T, I>),
+		/// Balance frozen. [currency_id, who, freeze_id, amount]
+		Frozen(CurrencyIdOf<T, I>, T::AccountId, LockIdentifier, BalanceOf<T, I>),
+		/// Freeze removed. [currency_id, who, freeze_id]
+		Thawed(CurrencyIdOf<T, I>, T::AccountId, LockIdentifier),
+		/// Repatriated reserve would have been dusted, the fallback was
+		/// applied instead. [currency_id, beneficiary, amount, fallback]
+		ReleaseFallbackApplied(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>, ReleaseFallback),
+		/// Deposit account derived. [parent, tag, deposit_account]
+		DepositAccountDerived(T::AccountId, u32, T::AccountId),
+		/// Deposit account swept to its parent. [currency_id,
+		/// deposit_account, amount]
+		DepositAccountSwept(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>),
+		/// Protocol sub-account registered. [sub_account, account]
+		SubAccountRegistered(SubAccount, T::AccountId),
+		/// Protocol sub-account deregistered. [sub_account]
+		SubAccountDeregistered(SubAccount),
+		/// Dust moved to the dust account. [currency_id, who, amount]
+		DustAggregated(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>),
+		/// Aggregated dust sent on at the end of an era. [currency_id, amount,
+		/// destination]
+		DustRedistributed(CurrencyIdOf<T, I>, BalanceOf<T, I>, DustDestination),
+		/// Balance checkpointing enabled. [currency_id]
+		CheckpointingEnabled(CurrencyIdOf<T, I>),
+		/// Checkpoints removed from a currency's child trie. [currency_id,
+		/// all_removed]
+		CheckpointsPruned(CurrencyIdOf<T, I>, bool),
+		/// Currency metadata set. [currency_id]
+		CurrencyMetadataSet(CurrencyIdOf<T, I>),
+		/// Transfer guard set. [currency_id, owner, guardian, threshold]
+		TransferGuardSet(CurrencyIdOf<T, I>, T::AccountId, T::AccountId, BalanceOf<T, I>),
+		/// Transfer guard removed. [currency_id, owner]
+		TransferGuardRemoved(CurrencyIdOf<T, I>, T::AccountId),
+		/// Guarded transfer waiting for confirmation. [transfer_id,
+		/// currency_id, from, to, amount]
+		TransferAwaitingConfirmation(u32, CurrencyIdOf<T, I>, T::AccountId, T::AccountId, BalanceOf<T, I>),
+		/// Guarded transfer confirmed and executed. [transfer_id]
+		TransferConfirmed(u32),
+		/// Guarded transfer not confirmed in time and dropped. [transfer_id]
+		TransferLapsed(u32),
+		/// Signed transfer authorization executed. [from, nonce]
+		AuthorizationUsed(T::AccountId, u64),
+		/// Delegate authorised. [currency_id, owner, delegate, max_per_period]
+		DelegateAdded(CurrencyIdOf<T, I>, T::AccountId, T::AccountId, BalanceOf<T, I>),
+		/// Delegate removed. [currency_id, owner, delegate]
+		DelegateRemoved(CurrencyIdOf<T, I>, T::AccountId, T::AccountId),
+		/// Scheduled reserve released. [currency_id, who, amount]
+		ReserveExpired(CurrencyIdOf<T, I>, T::AccountId, BalanceOf<T, I>),
+	}
+
+	/// Overrides `Config::GetStp258NativeId` when set.
+	#[pallet::storage]
+	#[pallet::getter(fn native_currency_id_override)]
+	pub type NativeCurrencyIdOverride<T: Config<I>, I: 'static = ()> = StorageValue<_, CurrencyIdOf<T, I>, OptionQuery>;
+
+	/// The id of the next announced balance update.
+	#[pallet::storage]
+	#[pallet::getter(fn next_update_id)]
+	pub type NextUpdateId<T: Config<I>, I: 'static = ()> = StorageValue<_, UpdateId, ValueQuery>;
+
+	/// Announced balance updates waiting for execution or veto.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_updates)]
+	pub type PendingUpdates<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, UpdateId, PendingUpdateOf<T, I>, OptionQuery>;
+
+	/// Where slashed funds of a currency are sent.
+	#[pallet::storage]
+	#[pallet::getter(fn slash_destination)]
+	pub type SlashDestinations<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, SlashDestination<T::AccountId>, ValueQuery>;
+
+	/// The order `slash` draws balances in, by currency.
+	#[pallet::storage]
+	#[pallet::getter(fn slash_order)]
+	pub type SlashOrders<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, SlashOrder, ValueQuery>;
+
+	/// Locks set through this pallet, by account and currency.
+	///
+	/// Locks set directly on the backing currencies are not recorded here.
+	#[pallet::storage]
+	#[pallet::getter(fn locks)]
+	pub type Locks<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyIdOf<T, I>,
+		Vec<BalanceLock<BalanceOf<T, I>>>,
+		ValueQuery,
+	>;
+
+	/// The sum of the largest lock set through this pallet on each account,
+	/// by currency.
+	#[pallet::storage]
+	#[pallet::getter(fn total_locked)]
+	pub type TotalLocked<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, BalanceOf<T, I>, ValueQuery>;
+
+	/// The total balance reserved through this pallet, by currency.
+	#[pallet::storage]
+	#[pallet::getter(fn total_reserved)]
+	pub type TotalReserved<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, BalanceOf<T, I>, ValueQuery>;
+
+	/// The most recent supply changes of a currency, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn supply_history)]
+	pub type SupplyHistory<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, Vec<SupplyChangeOf<T, I>>, ValueQuery>;
+
+	/// The number of balance operations of each account, carried by the
+	/// events of those operations.
+	#[pallet::storage]
+	#[pallet::getter(fn operation_nonce)]
+	pub type OperationNonces<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, u64, ValueQuery>;
+
+	/// The deposit accounts derived by users, by derived account.
+	#[pallet::storage]
+	#[pallet::getter(fn deposit_account)]
+	pub type DepositAccounts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, DepositAccount<T::AccountId>, OptionQuery>;
+
+	/// The active protocol sub-accounts and their derived accounts.
+	#[pallet::storage]
+	#[pallet::getter(fn registered_sub_account)]
+	pub type SubAccounts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, SubAccount, T::AccountId, OptionQuery>;
+
+	/// The dust aggregated since the end of the last era, by currency.
+	#[pallet::storage]
+	#[pallet::getter(fn accumulated_dust)]
+	pub type AccumulatedDust<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, BalanceOf<T, I>, ValueQuery>;
+
+	/// The number of currencies whose dust failed to be redistributed in
+	/// the pass under way, `None` when no pass is under way.
+	#[pallet::storage]
+	#[pallet::getter(fn dust_redistribution_pass)]
+	pub type DustRedistributionPass<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, OptionQuery>;
+
+	/// The total issuance of a currency sampled at era boundaries and SERP
+	/// adjustment points, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn issuance_history)]
+	pub type IssuanceHistory<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, Vec<(T::BlockNumber, BalanceOf<T, I>)>, ValueQuery>;
+
+	/// The storage release of the pallet, used to decide which migrations
+	/// to run.
+	#[pallet::storage]
+	#[pallet::getter(fn storage_version)]
+	pub(crate) type StorageVersion<T: Config<I>, I: 'static = ()> = StorageValue<_, Releases, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		pub serp_parameters: Vec<SerpParameterOf<T, I>>,
+		pub lock_prefixes: Vec<LockPrefix>,
+		pub slash_destinations: Vec<(CurrencyIdOf<T, I>, SlashDestination<T::AccountId>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
+		fn default() -> Self {
+			GenesisConfig {
+				serp_parameters: vec![],
+				lock_prefixes: vec![],
+				slash_destinations: vec![],
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
+		fn build(&self) {
+			StorageVersion::<T, I>::put(Releases::V2);
+			for parameter in &self.serp_parameters {
+				SerpParameters::<T, I>::insert(parameter.key(), parameter);
+			}
+			for prefix in &self.lock_prefixes {
+				LockPrefixes::<T, I>::insert(prefix, true);
+			}
+			for (currency_id, destination) in &self.slash_destinations {
+				SlashDestinations::<T, I>::insert(currency_id, destination);
+			}
+		}
+	}
+
+	/// SERP parameters set on chain, by key.
+	#[pallet::storage]
+	#[pallet::getter(fn serp_parameter)]
+	pub type SerpParameters<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, SerpParameterKey, SerpParameterOf<T, I>, OptionQuery>;
+
+	/// Registered prefixes of namespaced lock identifiers.
+	#[pallet::storage]
+	#[pallet::getter(fn lock_prefix_registered)]
+	pub type LockPrefixes<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, LockPrefix, bool, ValueQuery>;
+
+	/// The block at which a lock set with `set_lock_until` expires, by
+	/// account and (currency, lock id).
+	#[pallet::storage]
+	#[pallet::getter(fn lock_expiry)]
+	pub type LockExpiry<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(CurrencyIdOf<T, I>, LockIdentifier),
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// Locks to remove at a block.
+	#[pallet::storage]
+	#[pallet::getter(fn lock_expiry_queue)]
+	pub type LockExpiryQueue<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Vec<(T::AccountId, CurrencyIdOf<T, I>, LockIdentifier)>,
+		ValueQuery,
+	>;
+
+	/// The native issuance schedule, no native currency is issued if unset.
+	#[pallet::storage]
+	#[pallet::getter(fn issuance_schedule)]
+	pub type NativeIssuanceSchedule<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, IssuanceSchedule<BalanceOf<T, I>>, OptionQuery>;
+
+	/// The accounts credited with native issuance and their share of it.
+	///
+	/// Shares not allocated to any account are not issued.
+	#[pallet::storage]
+	#[pallet::getter(fn issuance_beneficiaries)]
+	pub type IssuanceBeneficiaries<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Vec<(T::AccountId, Perbill)>, ValueQuery>;
+
+	/// The native amount to issue at the start of the next era.
+	#[pallet::storage]
+	#[pallet::getter(fn next_era_issuance)]
+	pub type NextEraIssuance<T: Config<I>, I: 'static = ()> = StorageValue<_, BalanceOf<T, I>, ValueQuery>;
+
+	/// The share of usable balance charged as demurrage per block, by
+	/// currency.
+	#[pallet::storage]
+	#[pallet::getter(fn demurrage_rate)]
+	pub type DemurrageRates<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, Perbill, OptionQuery>;
+
+	/// Demurrage checkpoints and transfer volumes, by account and currency.
+	#[pallet::storage]
+	#[pallet::getter(fn account_activity)]
+	pub type AccountActivities<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyIdOf<T, I>,
+		AccountActivity<T::BlockNumber, BalanceOf<T, I>>,
+		ValueQuery,
+	>;
+
+	/// The interest paid on savings per block, by currency.
+	#[pallet::storage]
+	#[pallet::getter(fn savings_rate)]
+	pub type SavingsRates<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, Perbill, ValueQuery>;
+
+	/// The savings index of a currency and the block it was last accrued
+	/// at, an unset index is one.
+	#[pallet::storage]
+	#[pallet::getter(fn savings_index)]
+	pub type SavingsIndices<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, (FixedU128, T::BlockNumber), OptionQuery>;
+
+	/// Savings, by account and currency.
+	#[pallet::storage]
+	#[pallet::getter(fn savings)]
+	pub type Savings<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyIdOf<T, I>,
+		SavingsBalance<BalanceOf<T, I>>,
+		ValueQuery,
+	>;
+
+	/// Currencies whose transfer volume is recorded.
+	#[pallet::storage]
+	#[pallet::getter(fn volume_tracked)]
+	pub type VolumeTracked<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, bool, ValueQuery>;
+
+	/// How fee revenue is shared out.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_split)]
+	pub type FeeSplitConfig<T: Config<I>, I: 'static = ()> = StorageValue<_, FeeSplit, ValueQuery>;
+
+	/// Fee revenue collected, by era and currency, for the last
+	/// `FeeRevenueRetention` eras.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_revenue)]
+	pub type FeeRevenue<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, CurrencyIdOf<T, I>, BalanceOf<T, I>, ValueQuery>;
+
+	/// The block balance checkpointing was enabled at, by currency.
+	#[pallet::storage]
+	#[pallet::getter(fn checkpointed_since)]
+	pub type CheckpointedSince<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, T::BlockNumber, OptionQuery>;
+
+	/// The total balance of an account at the end of each block it changed
+	/// in, oldest first, by account and checkpointed currency.
+	#[pallet::storage]
+	#[pallet::getter(fn balance_checkpoints)]
+	pub type BalanceCheckpoints<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyIdOf<T, I>,
+		Vec<(T::BlockNumber, BalanceOf<T, I>)>,
+		ValueQuery,
+	>;
+
+	/// Currencies whose balance checkpoints are kept in a child trie of
+	/// their own instead of `BalanceCheckpoints`.
+	#[pallet::storage]
+	#[pallet::getter(fn child_trie_checkpointing)]
+	pub type ChildTrieCheckpointing<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, bool, ValueQuery>;
+
+	/// The display metadata of each currency.
+	#[pallet::storage]
+	#[pallet::getter(fn currency_metadata)]
+	pub type CurrencyMetadatas<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T, I>, CurrencyMetadata, OptionQuery>;
+
+	/// The guardian and threshold above which transfers need the
+	/// guardian's confirmation, by account and currency.
+	#[pallet::storage]
+	#[pallet::getter(fn transfer_guard)]
+	pub type TransferGuards<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyIdOf<T, I>,
+		(T::AccountId, BalanceOf<T, I>),
+		OptionQuery,
+	>;
+
+	/// The id of the next guarded transfer.
+	#[pallet::storage]
+	#[pallet::getter(fn next_transfer_id)]
+	pub type NextTransferId<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// Guarded transfers waiting for confirmation.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_transfer)]
+	pub type PendingTransfers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, PendingTransferOf<T, I>, OptionQuery>;
+
+	/// The next nonce of each payer's transfer authorizations.
+	#[pallet::storage]
+	#[pallet::getter(fn authorization_nonce)]
+	pub type AuthorizationNonces<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, u64, ValueQuery>;
+
+	/// Transfer budgets granted to delegates, by owner and (currency,
+	/// delegate).
+	#[pallet::storage]
+	#[pallet::getter(fn delegation)]
+	pub type Delegations<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(CurrencyIdOf<T, I>, T::AccountId),
+		Delegation<BalanceOf<T, I>>,
+		OptionQuery,
+	>;
+
+	/// Reserves to release at a block.
+	#[pallet::storage]
+	#[pallet::getter(fn reserve_expiry_queue)]
+	pub type ReserveExpiryQueue<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Vec<(T::AccountId, CurrencyIdOf<T, I>, BalanceOf<T, I>)>,
+		ValueQuery,
+	>;
+
+	/// Freezes, by account and currency. Unlike locks, freezes are not
+	/// recorded in `TotalLocked`.
+	#[pallet::storage]
+	#[pallet::getter(fn freezes)]
+	pub type Freezes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyIdOf<T, I>,
+		Vec<BalanceLock<BalanceOf<T, I>>>,
+		ValueQuery,
+	>;
+
+	/// Reserves held through `HoldableCurrency`, by account and currency.
+	#[pallet::storage]
+	#[pallet::getter(fn holds)]
+	pub type Holds<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyIdOf<T, I>,
+		Vec<(T::HoldReason, BalanceOf<T, I>)>,
+		ValueQuery,
+	>;
+
+	/// Currency snapshots, by the hash of their content.
+	#[pallet::storage]
+	#[pallet::getter(fn currency_snapshot)]
+	pub type CurrencySnapshots<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::Hash, CurrencySnapshotOf<T, I>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<T::BlockNumber> for Pallet<T, I> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expiring = LockExpiryQueue::<T, I>::take(now);
+			let count = expiring.len() as u32;
+			for (who, currency_id, lock_id) in expiring {
+				// the lock may have been re-set or removed since it was queued
+				if Self::lock_expiry(&who, (currency_id, lock_id)) == Some(now) {
+					if let Err(e) =
+						<Self as Stp258CurrencyLockable<T::AccountId>>::remove_lock(lock_id, currency_id, &who)
+					{
+						log::error!(
+							target: LOG_TARGET,
+							"failed to remove expired lock {:?} of {:?} under {:?}: {:?}",
+							lock_id,
+							who,
+							currency_id,
+							e,
+						);
+					}
+					Self::deposit_event(Event::LockExpired(currency_id, who, lock_id));
+				}
+			}
+			T::WeightInfo::expire_locks(count)
+				.saturating_add(Self::expire_reserves(now))
+				.saturating_add(Self::issue_native(now))
+				.saturating_add(Self::sample_issuance(now))
+				.saturating_add(Self::redistribute_dust(now))
+				.saturating_add(Self::prune_fee_revenue(now))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Transfer some balance to another account under `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_non_native_currency())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T, I>,
+			#[pallet::compact] amount: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			Self::guarded_transfer(currency_id, &from, &to, amount)?;
+			Ok(().into())
+		}
+
+		/// Transfer some native currency to another account.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_native_currency())]
+		pub fn transfer_native_currency(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			Self::guarded_transfer(Self::native_currency_id(), &from, &to, amount)?;
+			Ok(().into())
+		}
+
+		/// Transfer all of the caller's `currency_id` that can be moved to
+		/// another account, leaving exactly the existential deposit behind.
+		///
+		/// Balance restricted by locks or freezes stays in place, so the
+		/// caller's account is never reaped.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_all_keep_alive())]
+		pub fn transfer_all_keep_alive(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let above_minimum = <Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, &from)
+				.saturating_sub(<Self as Stp258Currency<T::AccountId>>::minimum_balance(currency_id));
+			let amount = Self::usable_balance(currency_id, &from).min(above_minimum);
+			Self::guarded_transfer(currency_id, &from, &to, amount)?;
+			Ok(().into())
+		}
+
+		/// update amount of account `who` under `currency_id`.
+		///
+		/// The dispatch origin of this call must be `MonetaryOrigin`.
+		#[pallet::weight(T::WeightInfo::update_balance_non_native_currency())]
+		pub fn update_balance(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T, I>,
+			amount: AmountOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryOrigin::ensure_origin(origin)?;
+			let dest = T::Lookup::lookup(who)?;
+			<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
+			Ok(().into())
+		}
+
+		/// Announce an update of the amount of account `who` under
+		/// `currency_id`, executable once `UpdateDelay` blocks have passed.
+		///
+		/// The dispatch origin of this call must be `MonetaryOrigin`.
+		#[pallet::weight(T::WeightInfo::announce_update())]
+		pub fn announce_update(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T, I>,
+			amount: AmountOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let id = Self::next_update_id();
+			let next_id = id.checked_add(1).ok_or(Error::<T, I>::NoAvailableUpdateId)?;
+			let unlock_at = <frame_system::Module<T>>::block_number().saturating_add(T::UpdateDelay::get());
+
+			NextUpdateId::<T, I>::put(next_id);
+			PendingUpdates::<T, I>::insert(
+				id,
+				PendingUpdate {
+					who: who.clone(),
+					currency_id,
+					amount,
+					unlock_at,
+				},
+			);
+			Self::deposit_event(Event::UpdateAnnounced(id, currency_id, who, amount, unlock_at));
+			Ok(().into())
+		}
+
+		/// Execute an announced balance update whose delay has passed.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::weight(T::WeightInfo::execute_update())]
+		pub fn execute_update(origin: OriginFor<T>, update_id: UpdateId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let update = Self::pending_updates(update_id).ok_or(Error::<T, I>::UpdateNotFound)?;
+			ensure!(
+				<frame_system::Module<T>>::block_number() >= update.unlock_at,
+				Error::<T, I>::UpdateNotUnlocked
+			);
+
+			<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(
+				update.currency_id,
+				&update.who,
+				update.amount,
+			)?;
+			PendingUpdates::<T, I>::remove(update_id);
+			Self::deposit_event(Event::UpdateExecuted(update_id));
+			Ok(().into())
+		}
+
+		/// Veto an announced balance update.
+		///
+		/// The dispatch origin of this call must be `EmergencyOrigin`.
+		#[pallet::weight(T::WeightInfo::veto_update())]
+		pub fn veto_update(origin: OriginFor<T>, update_id: UpdateId) -> DispatchResultWithPostInfo {
+			T::EmergencyOrigin::ensure_origin(origin)?;
+			ensure!(
+				PendingUpdates::<T, I>::contains_key(update_id),
+				Error::<T, I>::UpdateNotFound
+			);
+
+			PendingUpdates::<T, I>::remove(update_id);
+			Self::deposit_event(Event::UpdateVetoed(update_id));
+			Ok(().into())
+		}
+
+		/// Set where slashed funds of `currency_id` are sent.
+		///
+		/// The dispatch origin of this call must be `MonetaryOrigin`.
+		#[pallet::weight(T::WeightInfo::set_slash_destination())]
+		pub fn set_slash_destination(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			destination: SlashDestination<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryOrigin::ensure_origin(origin)?;
+			SlashDestinations::<T, I>::insert(currency_id, destination.clone());
+			Self::deposit_event(Event::SlashDestinationSet(currency_id, destination));
+			Ok(().into())
+		}
+
+		/// Set the order `slash` draws free and reserved balance of
+		/// `currency_id` in.
+		///
+		/// The dispatch origin of this call must be `MonetaryOrigin`.
+		#[pallet::weight(T::WeightInfo::set_slash_order())]
+		pub fn set_slash_order(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			order: SlashOrder,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryOrigin::ensure_origin(origin)?;
+			if order == SlashOrder::default() {
+				SlashOrders::<T, I>::remove(currency_id);
+			} else {
+				SlashOrders::<T, I>::insert(currency_id, order);
+			}
+			Self::deposit_event(Event::SlashOrderSet(currency_id, order));
+			Ok(().into())
+		}
+
+		/// Set the currency id routed to `Stp258Native`, or restore
+		/// `GetStp258NativeId` with `None`.
+		///
+		/// Setting the current native currency id again changes nothing and
+		/// emits no event.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::set_native_currency_id())]
+		pub fn set_native_currency_id(
+			origin: OriginFor<T>,
+			currency_id: Option<CurrencyIdOf<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let old_currency_id = Self::native_currency_id();
+			if currency_id.unwrap_or_else(T::GetStp258NativeId::get) == old_currency_id {
+				return Ok(().into());
+			}
+			match currency_id {
+				Some(currency_id) => NativeCurrencyIdOverride::<T, I>::put(currency_id),
+				None => NativeCurrencyIdOverride::<T, I>::kill(),
+			}
+			Self::deposit_event(Event::NativeCurrencyIdMigrated(
+				old_currency_id,
+				Self::native_currency_id(),
+			));
+			Ok(().into())
+		}
+
+		/// Set a SERP parameter, overriding its compile-time default.
+		///
+		/// The dispatch origin of this call must be `SerpOrigin`.
+		#[pallet::weight(T::WeightInfo::set_serp_parameter())]
+		pub fn set_serp_parameter(origin: OriginFor<T>, parameter: SerpParameterOf<T, I>) -> DispatchResultWithPostInfo {
+			T::SerpOrigin::ensure_origin(origin)?;
+			SerpParameters::<T, I>::insert(parameter.key(), parameter);
+			Self::deposit_event(Event::SerpParameterSet(parameter));
+			Ok(().into())
+		}
+
+		/// Adjust the supply of `currency_id` by `amount`, minting into or
+		/// burning from `TreasuryAccount`.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::governed_adjust_supply())]
+		pub fn governed_adjust_supply(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			amount: AmountOf<T, I>,
+			rationale: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(
+				currency_id,
+				&T::TreasuryAccount::get(),
+				amount,
+			)?;
+			Self::record_supply_change(currency_id, amount, SupplyChangeSource::Governance(rationale));
+			#[cfg(feature = "metrics")]
+			T::Metrics::on_serp_action(currency_id);
+			Self::deposit_event(Event::SupplyAdjusted(currency_id, amount, rationale));
+			Ok(().into())
+		}
+
+		/// Set the native issuance schedule and its beneficiaries, `None`
+		/// stops issuance.
+		///
+		/// The first era issued is the next one to start.
+		///
+		/// The dispatch origin of this call must be `MonetaryOrigin`.
+		#[pallet::weight(T::WeightInfo::set_issuance_schedule())]
+		pub fn set_issuance_schedule(
+			origin: OriginFor<T>,
+			schedule: Option<IssuanceSchedule<BalanceOf<T, I>>>,
+			beneficiaries: Vec<(T::AccountId, Perbill)>,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryOrigin::ensure_origin(origin)?;
+			ensure!(
+				beneficiaries.len() as u32 <= T::MaxIssuanceBeneficiaries::get(),
+				Error::<T, I>::TooManyIssuanceBeneficiaries
+			);
+			let shares = beneficiaries
+				.iter()
+				.fold(0u64, |total, (_, share)| total.saturating_add(share.deconstruct() as u64));
+			ensure!(
+				shares <= Perbill::one().deconstruct() as u64,
+				Error::<T, I>::InvalidIssuanceShares
+			);
+
+			match &schedule {
+				Some(schedule) => {
+					NativeIssuanceSchedule::<T, I>::put(schedule);
+					NextEraIssuance::<T, I>::put(schedule.initial());
+				}
+				None => {
+					NativeIssuanceSchedule::<T, I>::kill();
+					NextEraIssuance::<T, I>::kill();
+				}
+			}
+			IssuanceBeneficiaries::<T, I>::put(beneficiaries);
+			Self::deposit_event(Event::IssuanceScheduleSet(schedule));
+			Ok(().into())
+		}
+
+		/// Set the share of usable balance charged as demurrage per block on
+		/// `currency_id`, `None` disables demurrage.
+		///
+		/// The dispatch origin of this call must be `MonetaryOrigin`.
+		#[pallet::weight(T::WeightInfo::set_demurrage_rate())]
+		pub fn set_demurrage_rate(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			rate: Option<Perbill>,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryOrigin::ensure_origin(origin)?;
+			match rate {
+				Some(rate) => DemurrageRates::<T, I>::insert(currency_id, rate),
+				None => DemurrageRates::<T, I>::remove(currency_id),
+			}
+			Self::deposit_event(Event::DemurrageRateSet(currency_id, rate));
+			Ok(().into())
+		}
+
+		/// Set the interest paid per block on savings in `currency_id`.
+		///
+		/// Interest is paid from `TreasuryAccount` when savings are
+		/// withdrawn.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::set_savings_rate())]
+		pub fn set_savings_rate(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			rate: Perbill,
+		) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			// interest so far accrues at the old rate
+			Self::accrue_savings_index(currency_id);
+			SavingsRates::<T, I>::insert(currency_id, rate);
+			Self::deposit_event(Event::SavingsRateSet(currency_id, rate));
+			Ok(().into())
+		}
+
+		/// Move `amount` of `currency_id` from the caller's free balance to
+		/// their savings.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::weight(T::WeightInfo::deposit_to_savings())]
+		pub fn deposit_to_savings(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			#[pallet::compact] amount: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let index = Self::accrue_savings_index(currency_id);
+			<Self as Stp258CurrencyReservable<T::AccountId>>::reserve(currency_id, &who, amount)?;
+			let shares = Self::savings_shares(index, amount);
+			Savings::<T, I>::mutate(&who, currency_id, |savings| {
+				savings.shares = savings.shares.saturating_add(shares);
+				savings.principal = savings.principal.saturating_add(amount);
+			});
+			Self::deposit_event(Event::SavingsDeposited(currency_id, who, amount));
+			Ok(().into())
+		}
+
+		/// Move `amount` of `currency_id`, interest included, from the
+		/// caller's savings to their free balance.
+		///
+		/// Interest is paid by `Config::TreasuryAccount`. If it cannot pay,
+		/// the principal is still withdrawn and the interest is left in
+		/// savings.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::weight(T::WeightInfo::withdraw_from_savings())]
+		pub fn withdraw_from_savings(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			#[pallet::compact] amount: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let index = Self::accrue_savings_index(currency_id);
+			let savings = Self::savings(&who, currency_id);
+			let value: BalanceOf<T, I> = index.saturating_mul_int(savings.shares.saturated_into::<u128>()).saturated_into();
+			ensure!(
+				!savings.shares.is_zero() && amount <= value,
+				Error::<T, I>::InsufficientSavings
+			);
+
+			let mut shares = if amount == value {
+				savings.shares
+			} else {
+				// round against the saver
+				Self::savings_shares(index, amount)
+					.saturating_add(One::one())
+					.min(savings.shares)
+			};
+			let principal: BalanceOf<T, I> = FixedU128::saturating_from_rational(
+				shares.saturated_into::<u128>(),
+				savings.shares.saturated_into::<u128>(),
+			)
+			.saturating_mul_int(savings.principal.saturated_into::<u128>())
+			.saturated_into();
+			let principal = principal.min(amount);
+			let interest = amount.saturating_sub(principal);
+
+			// the principal is the saver's own, so it is never held back by
+			// the treasury failing to pay the interest
+			<Self as Stp258CurrencyReservable<T::AccountId>>::unreserve(currency_id, &who, principal);
+			let mut withdrawn = amount;
+			if !interest.is_zero() {
+				if let Err(e) = with_transaction_result(|| {
+					<Self as Stp258Currency<T::AccountId>>::transfer(
+						currency_id,
+						&T::TreasuryAccount::get(),
+						&who,
+						interest,
+					)
+				}) {
+					// the unpaid interest stays in savings, rounded against the saver
+					withdrawn = principal;
+					shares = shares.saturating_sub(Self::savings_shares(index, interest));
+					log::warn!(
+						target: LOG_TARGET,
+						"savings interest {:?} of {:?} under {:?} left unpaid: {:?}",
+						interest,
+						who,
+						currency_id,
+						e,
+					);
+					Self::deposit_event(Event::SavingsInterestUnpaid(currency_id, who.clone(), interest));
+				}
+			}
+			if shares == savings.shares {
+				Savings::<T, I>::remove(&who, currency_id);
+			} else {
+				Savings::<T, I>::insert(
+					&who,
+					currency_id,
+					SavingsBalance {
+						shares: savings.shares.saturating_sub(shares),
+						principal: savings.principal.saturating_sub(principal),
+					},
+				);
+			}
+			Self::deposit_event(Event::SavingsWithdrawn(currency_id, who, withdrawn));
+			Ok(().into())
+		}
+
+		/// Enable or disable recording the transfer volume of
+		/// `currency_id`.
+		///
+		/// The dispatch origin of this call must be `MonetaryOrigin`.
+		#[pallet::weight(T::WeightInfo::set_volume_tracking())]
+		pub fn set_volume_tracking(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			enabled: bool,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryOrigin::ensure_origin(origin)?;
+			if enabled {
+				VolumeTracked::<T, I>::insert(currency_id, true);
+			} else {
+				VolumeTracked::<T, I>::remove(currency_id);
+			}
+			Self::deposit_event(Event::VolumeTrackingSet(currency_id, enabled));
+			Ok(().into())
+		}
+
+		/// Set how fee revenue is shared between the treasury, burning and
+		/// the serpers.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::set_fee_split())]
+		pub fn set_fee_split(origin: OriginFor<T>, fee_split: FeeSplit) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(fee_split.is_valid(), Error::<T, I>::InvalidFeeSplit);
+			FeeSplitConfig::<T, I>::put(fee_split);
+			Self::deposit_event(Event::FeeSplitSet(fee_split));
+			Ok(().into())
+		}
+
+		/// Store a snapshot of the monetary state of `currency_id`, under
+		/// the hash of its content.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::snapshot_currency())]
+		pub fn snapshot_currency(origin: OriginFor<T>, currency_id: CurrencyIdOf<T, I>) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let snapshot = CurrencySnapshot {
+				currency_id,
+				total_issuance: <Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id),
+				treasury_balance: <Self as Stp258Currency<T::AccountId>>::total_balance(
+					currency_id,
+					&T::TreasuryAccount::get(),
+				),
+				slash_destination: Self::slash_destination(currency_id),
+				demurrage_rate: Self::demurrage_rate(currency_id),
+				savings_rate: Self::savings_rate(currency_id),
+				volume_tracked: Self::volume_tracked(currency_id),
+			};
+			let snapshot_id = T::Hashing::hash_of(&snapshot);
+			CurrencySnapshots::<T, I>::insert(snapshot_id, snapshot);
+			Self::deposit_event(Event::CurrencySnapshotTaken(currency_id, snapshot_id));
+			Ok(().into())
+		}
+
+		/// Restore the parameters of the currency in snapshot `snapshot_id`.
+		///
+		/// Issuance and treasury balance are recorded in the snapshot for
+		/// comparison only and are not restored.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::restore_currency())]
+		pub fn restore_currency(origin: OriginFor<T>, snapshot_id: T::Hash) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let snapshot = Self::currency_snapshot(snapshot_id).ok_or(Error::<T, I>::UnknownSnapshot)?;
+			let currency_id = snapshot.currency_id;
+			SlashDestinations::<T, I>::insert(currency_id, snapshot.slash_destination);
+			match snapshot.demurrage_rate {
+				Some(rate) => DemurrageRates::<T, I>::insert(currency_id, rate),
+				None => DemurrageRates::<T, I>::remove(currency_id),
+			}
+			// interest so far accrues at the old rate
+			Self::accrue_savings_index(currency_id);
+			SavingsRates::<T, I>::insert(currency_id, snapshot.savings_rate);
+			if snapshot.volume_tracked {
+				VolumeTracked::<T, I>::insert(currency_id, true);
+			} else {
+				VolumeTracked::<T, I>::remove(currency_id);
+			}
+			Self::deposit_event(Event::CurrencyRestored(currency_id, snapshot_id));
+			Ok(().into())
+		}
+
+		/// Start checkpointing the balances of `currency_id` changed through
+		/// this pallet, for `balance_at`.
+		///
+		/// Checkpointing cannot be disabled again, so checkpoints never go
+		/// stale.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::enable_checkpointing())]
+		pub fn enable_checkpointing(origin: OriginFor<T>, currency_id: CurrencyIdOf<T, I>) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			Self::do_enable_checkpointing(currency_id)?;
+			Ok(().into())
+		}
+
+		/// Start checkpointing the balances of `currency_id` like
+		/// `enable_checkpointing`, keeping the checkpoints in a child trie of
+		/// the currency's own.
+		///
+		/// Meant for currencies with many holders: the child trie has its
+		/// own root for proofs over all holders, and `prune_checkpoints` can
+		/// drop it whole.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::enable_child_trie_checkpointing())]
+		pub fn enable_child_trie_checkpointing(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			Self::do_enable_checkpointing(currency_id)?;
+			ChildTrieCheckpointing::<T, I>::insert(currency_id, true);
+			Ok(().into())
+		}
+
+		/// Stop checkpointing `currency_id` and remove up to `limit` of its
+		/// checkpoints from its child trie.
+		///
+		/// Call again until `CheckpointsPruned` reports all checkpoints
+		/// removed; checkpointing can be enabled again after that.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::prune_checkpoints(*limit))]
+		pub fn prune_checkpoints(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				Self::child_trie_checkpointing(currency_id),
+				Error::<T, I>::CheckpointsNotInChildTrie
+			);
+			CheckpointedSince::<T, I>::remove(currency_id);
+			let all_removed = child::kill_storage(&Self::checkpoints_child_info(currency_id), Some(limit));
+			if all_removed {
+				ChildTrieCheckpointing::<T, I>::remove(currency_id);
+			}
+			Self::deposit_event(Event::CheckpointsPruned(currency_id, all_removed));
+			Ok(().into())
+		}
+
+		/// Set the name and symbol of `currency_id`.
+		///
+		/// The dispatch origin of this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::set_currency_metadata())]
+		pub fn set_currency_metadata(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let max_length = T::MaxMetadataLength::get() as usize;
+			ensure!(
+				name.len() <= max_length && symbol.len() <= max_length,
+				Error::<T, I>::MetadataTooLong
+			);
+			CurrencyMetadatas::<T, I>::insert(currency_id, CurrencyMetadata { name, symbol });
+			Self::deposit_event(Event::CurrencyMetadataSet(currency_id));
+			Ok(().into())
+		}
+
+		/// Derive the caller's deposit account for `tag`, see
+		/// `deposit_account_of`.
+		///
+		/// With `auto_sweep`, non-native currency transferred or deposited
+		/// into it through this pallet is forwarded to the caller.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::weight(T::WeightInfo::derive_deposit_account())]
+		pub fn derive_deposit_account(origin: OriginFor<T>, tag: u32, auto_sweep: bool) -> DispatchResultWithPostInfo {
+			let parent = ensure_signed(origin)?;
+			let account = Self::deposit_account_of(&parent, tag);
+			ensure!(
+				!DepositAccounts::<T, I>::contains_key(&account),
+				Error::<T, I>::DepositAccountExists
+			);
+			DepositAccounts::<T, I>::insert(
+				&account,
+				DepositAccount {
+					parent: parent.clone(),
+					tag,
+					auto_sweep,
+				},
+			);
+			Self::deposit_event(Event::DepositAccountDerived(parent, tag, account));
+			Ok(().into())
+		}
+
+		/// Move the usable `currency_id` of one of the caller's deposit
+		/// accounts to the caller.
+		///
+		/// The dispatch origin for this call must be `Signed` by the parent
+		/// of `deposit_account`.
+		#[pallet::weight(T::WeightInfo::sweep_deposit_account())]
+		pub fn sweep_deposit_account(
+			origin: OriginFor<T>,
+			deposit_account: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let parent = ensure_signed(origin)?;
+			let deposit_account = T::Lookup::lookup(deposit_account)?;
+			ensure!(
+				Self::deposit_account(&deposit_account).map(|account| account.parent) == Some(parent.clone()),
+				Error::<T, I>::NotDepositAccountOwner
+			);
+			let amount = Self::usable_balance(currency_id, &deposit_account);
+			let swept = Self::transfer_returning_actual(currency_id, &deposit_account, &parent, amount)?;
+			Self::deposit_event(Event::DepositAccountSwept(currency_id, deposit_account, swept));
+			Ok(().into())
+		}
+
+		/// Require `guardian`'s confirmation for the caller's transfers of
+		/// more than `threshold` of `currency_id`.
+		///
+		/// A guard can only be removed by its guardian.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::weight(T::WeightInfo::set_transfer_guard())]
+		pub fn set_transfer_guard(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			guardian: <T::Lookup as StaticLookup>::Source,
+			threshold: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let guardian = T::Lookup::lookup(guardian)?;
+			ensure!(
+				!TransferGuards::<T, I>::contains_key(&owner, currency_id),
+				Error::<T, I>::TransferGuardExists
+			);
+			TransferGuards::<T, I>::insert(&owner, currency_id, (guardian.clone(), threshold));
+			Self::deposit_event(Event::TransferGuardSet(currency_id, owner, guardian, threshold));
+			Ok(().into())
+		}
+
+		/// Remove the transfer guard of `owner` in `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// guardian.
+		#[pallet::weight(T::WeightInfo::remove_transfer_guard())]
+		pub fn remove_transfer_guard(
+			origin: OriginFor<T>,
+			owner: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let guardian = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let (expected, _) = Self::transfer_guard(&owner, currency_id).ok_or(Error::<T, I>::NoTransferGuard)?;
+			ensure!(guardian == expected, Error::<T, I>::NotGuardian);
+			TransferGuards::<T, I>::remove(&owner, currency_id);
+			Self::deposit_event(Event::TransferGuardRemoved(currency_id, owner));
+			Ok(().into())
+		}
+
+		/// Execute guarded transfer `transfer_id`, or drop it if its
+		/// confirmation window has passed.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// guardian the transfer was queued under, or by anyone once the
+		/// transfer has lapsed.
+		#[pallet::weight(T::WeightInfo::confirm_transfer())]
+		pub fn confirm_transfer(origin: OriginFor<T>, transfer_id: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let transfer = Self::pending_transfer(transfer_id).ok_or(Error::<T, I>::UnknownPendingTransfer)?;
+			if <frame_system::Module<T>>::block_number() > transfer.expires_at {
+				PendingTransfers::<T, I>::remove(transfer_id);
+				Self::deposit_event(Event::TransferLapsed(transfer_id));
+				return Ok(().into());
+			}
+			// the guard may have been removed or replaced since
+			ensure!(who == transfer.guardian, Error::<T, I>::NotGuardian);
+			<Self as Stp258Currency<T::AccountId>>::transfer(
+				transfer.currency_id,
+				&transfer.from,
+				&transfer.to,
+				transfer.amount,
+			)?;
+			PendingTransfers::<T, I>::remove(transfer_id);
+			Self::deposit_event(Event::TransferConfirmed(transfer_id));
+			Ok(().into())
+		}
+
+		/// Authorise `delegate` to transfer up to `max_per_period` of the
+		/// caller's `currency_id` per era, replacing any earlier budget.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::weight(T::WeightInfo::add_delegate())]
+		pub fn add_delegate(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			max_per_period: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			Delegations::<T, I>::insert(
+				&owner,
+				(currency_id, delegate.clone()),
+				Delegation {
+					max_per_period,
+					era: Self::era_of(<frame_system::Module<T>>::block_number()),
+					spent: Zero::zero(),
+				},
+			);
+			Self::deposit_event(Event::DelegateAdded(currency_id, owner, delegate, max_per_period));
+			Ok(().into())
+		}
+
+		/// Revoke the caller's delegation of `currency_id` to `delegate`.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::weight(T::WeightInfo::remove_delegate())]
+		pub fn remove_delegate(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T, I>,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			ensure!(
+				Delegations::<T, I>::contains_key(&owner, (currency_id, delegate.clone())),
+				Error::<T, I>::NotDelegate
+			);
+			Delegations::<T, I>::remove(&owner, (currency_id, delegate.clone()));
+			Self::deposit_event(Event::DelegateRemoved(currency_id, owner, delegate));
+			Ok(().into())
+		}
+
+		/// Transfer `amount` of `owner`'s `currency_id` to `dest`, within the
+		/// budget `owner` granted the caller for the current era.
+		///
+		/// Transfers above `owner`'s transfer guard wait for the guardian as
+		/// usual.
+		///
+		/// The dispatch origin for this call must be `Signed` by a delegate
+		/// of `owner`.
+		#[pallet::weight(T::WeightInfo::delegated_transfer())]
+		pub fn delegated_transfer(
+			origin: OriginFor<T>,
+			owner: <T::Lookup as StaticLookup>::Source,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T, I>,
+			#[pallet::compact] amount: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let mut delegation =
+				Self::delegation(&owner, (currency_id, delegate.clone())).ok_or(Error::<T, I>::NotDelegate)?;
+			let era = Self::era_of(<frame_system::Module<T>>::block_number());
+			if delegation.era != era {
+				delegation.era = era;
+				delegation.spent = Zero::zero();
+			}
+			delegation.spent = delegation.spent.saturating_add(amount);
+			ensure!(
+				delegation.spent <= delegation.max_per_period,
+				Error::<T, I>::DelegationBudgetExceeded
+			);
+			// transfers above the owner's guard wait for the guardian, budget spent
+			Self::guarded_transfer(currency_id, &owner, &dest, amount)?;
+			Delegations::<T, I>::insert(&owner, (currency_id, delegate), delegation);
+			Ok(().into())
+		}
+
+		/// Execute `authorization`, a transfer signed offline by its payer.
+		///
+		/// Each of the payer's authorizations is used at most once and in
+		/// nonce order. Transfers above the payer's transfer guard wait for
+		/// the guardian as usual.
+		///
+		/// The dispatch origin for this call must be `Signed`, by anyone.
+		#[pallet::weight(T::WeightInfo::transfer_with_authorization())]
+		pub fn transfer_with_authorization(
+			origin: OriginFor<T>,
+			authorization: TransferAuthorizationOf<T, I>,
+			signature: T::AuthorizationSignature,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(
+				signature.verify(&Self::authorization_payload(&authorization)[..], &authorization.from),
+				Error::<T, I>::InvalidAuthorizationSignature
+			);
+			ensure!(
+				<frame_system::Module<T>>::block_number() <= authorization.deadline,
+				Error::<T, I>::AuthorizationExpired
+			);
+			let nonce = Self::authorization_nonce(&authorization.from);
+			ensure!(authorization.nonce == nonce, Error::<T, I>::InvalidAuthorizationNonce);
+			Self::guarded_transfer(
+				authorization.currency_id,
+				&authorization.from,
+				&authorization.dest,
+				authorization.amount,
+			)?;
+			AuthorizationNonces::<T, I>::insert(&authorization.from, nonce.saturating_add(1));
+			Self::deposit_event(Event::AuthorizationUsed(authorization.from, nonce));
+			Ok(().into())
+		}
+
+		/// Register `prefix` for namespaced lock identifiers.
+		///
+		/// The dispatch origin of this call must be `ComplianceOrigin`.
+		#[pallet::weight(T::WeightInfo::register_lock_prefix())]
+		pub fn register_lock_prefix(origin: OriginFor<T>, prefix: LockPrefix) -> DispatchResultWithPostInfo {
+			T::ComplianceOrigin::ensure_origin(origin)?;
+			ensure!(
+				!Self::lock_prefix_registered(prefix),
+				Error::<T, I>::LockPrefixAlreadyRegistered
+			);
+			LockPrefixes::<T, I>::insert(prefix, true);
+			Self::deposit_event(Event::LockPrefixRegistered(prefix));
+			Ok(().into())
+		}
+
+		/// Deregister `prefix`, new locks can no longer use it.
+		///
+		/// The dispatch origin of this call must be `ComplianceOrigin`.
+		#[pallet::weight(T::WeightInfo::deregister_lock_prefix())]
+		pub fn deregister_lock_prefix(origin: OriginFor<T>, prefix: LockPrefix) -> DispatchResultWithPostInfo {
+			T::ComplianceOrigin::ensure_origin(origin)?;
+			ensure!(
+				Self::lock_prefix_registered(prefix),
+				Error::<T, I>::UnregisteredLockPrefix
+			);
+			LockPrefixes::<T, I>::remove(prefix);
+			Self::deposit_event(Event::LockPrefixDeregistered(prefix));
+			Ok(().into())
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Config<I>, I: 'static> GenesisConfig<T, I> {
+	/// The genesis config named `name`, either `"devnet"` or `"mainnet"`.
+	pub fn preset(name: &str) -> Option<Self> {
+		match name {
+			"devnet" => Some(Self::devnet()),
+			"mainnet" => Some(Self::mainnet()),
+			_ => None,
+		}
+	}
+
+	/// SERP parameters for local development, adjusting every 10 blocks.
+	pub fn devnet() -> Self {
+		GenesisConfig {
+			serp_parameters: Self::serp_parameters_adjusting_every(10),
+			..Default::default()
+		}
+	}
+
+	/// SERP parameters for production, adjusting every 600 blocks.
+	pub fn mainnet() -> Self {
+		GenesisConfig {
+			serp_parameters: Self::serp_parameters_adjusting_every(600),
+			..Default::default()
+		}
+	}
+
+	fn serp_parameters_adjusting_every(frequency: u32) -> Vec<SerpParameterOf<T, I>> {
+		vec![
+			SerpParameter::AdjustmentFrequency(frequency.into()),
+			SerpParameter::SerpQuoteMultiple(2u32.into()),
+			SerpParameter::SerperRatio(Perbill::from_percent(25)),
+			SerpParameter::SettPayRatio(Perbill::from_percent(75)),
+		]
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The currency id routed to `Stp258Native`.
+	pub fn native_currency_id() -> CurrencyIdOf<T, I> {
+		Self::native_currency_id_override().unwrap_or_else(T::GetStp258NativeId::get)
+	}
+
+	/// Transfer `amount` of `currency_id` from `from` to `to`, or queue it
+	/// for the guardian's confirmation if it exceeds `from`'s transfer
+	/// guard.
+	fn guarded_transfer(
+		currency_id: CurrencyIdOf<T, I>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T, I>,
+	) -> DispatchResult {
+		match Self::transfer_guard(from, currency_id) {
+			Some((guardian, threshold)) if amount > threshold => {
+				let id = Self::next_transfer_id();
+				let next_id = id.checked_add(1).ok_or(Error::<T, I>::NoAvailableTransferId)?;
+				NextTransferId::<T, I>::put(next_id);
+				let expires_at =
+					<frame_system::Module<T>>::block_number().saturating_add(T::TransferConfirmationWindow::get());
+				PendingTransfers::<T, I>::insert(
+					id,
+					PendingTransfer {
+						from: from.clone(),
+						to: to.clone(),
+						guardian,
+						currency_id,
+						amount,
+						expires_at,
+					},
+				);
+				Self::deposit_event(Event::TransferAwaitingConfirmation(
+					id,
+					currency_id,
+					from.clone(),
+					to.clone(),
+					amount,
+				));
+				Ok(())
+			}
+			_ => Self::transfer_returning_actual(currency_id, from, to, amount).map(|_| ()),
+		}
+	}
+
+	/// Emit `trace` for `currency_id`, if built with the `trace-events`
+	/// feature.
+	fn trace(currency_id: CurrencyIdOf<T, I>, trace: Trace<BalanceOf<T, I>>) {
+		if cfg!(feature = "trace-events") {
+			Self::deposit_event(Event::Traced(currency_id, trace));
+		}
+	}
+
+	/// Trace which backend `currency_id` is routed to.
+	fn trace_route(currency_id: CurrencyIdOf<T, I>) {
+		if currency_id == Self::native_currency_id() {
+			Self::trace(currency_id, Trace::RoutedNative);
+		} else {
+			Self::trace(currency_id, Trace::RoutedNonNative);
+		}
+	}
+
+	/// The block of the next SERP adjustment of `currency_id`, after the
+	/// current block.
+	///
+	/// `None` for the native currency, which is not adjusted, and if no
+	/// `AdjustmentFrequency` is set through `set_serp_parameter`.
+	pub fn next_adjustment_block(currency_id: CurrencyIdOf<T, I>) -> Option<T::BlockNumber> {
+		if currency_id == Self::native_currency_id() {
+			return None;
+		}
+		let frequency = match Self::serp_parameter(SerpParameterKey::AdjustmentFrequency) {
+			Some(SerpParameter::AdjustmentFrequency(frequency)) if !frequency.is_zero() => frequency,
+			_ => return None,
+		};
+		let now = <frame_system::Module<T>>::block_number();
+		(now / frequency).saturating_add(One::one()).checked_mul(&frequency)
+	}
+
+	/// Transfer `amount` of `currency_id` from `from` to `to`, returning the
+	/// amount `to` was actually credited with.
+	///
+	/// The credited amount may differ from `amount` when the backing
+	/// currency applies existential deposit or dust rules.
+	pub fn transfer_returning_actual(
+		currency_id: CurrencyIdOf<T, I>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T, I>,
+	) -> result::Result<BalanceOf<T, I>, DispatchError> {
+		if amount.is_zero() || from == to {
+			return Ok(Zero::zero());
+		}
+		// demurrage is charged on the balances before the transfer, and only
+		// kept if the transfer succeeds
+		let actual = with_transaction_result(|| {
+			Self::charge_demurrage(currency_id, from);
+			Self::charge_demurrage(currency_id, to);
+			let before = <Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, to);
+			Self::trace_route(currency_id);
+			Self::track_checkpoints(currency_id, &[from, to], || {
+				if currency_id == Self::native_currency_id() {
+					T::Stp258Native::transfer(from, to, amount)
+				} else {
+					T::Stp258Currency::transfer(currency_id, from, to, amount)
+				}
+			})?;
+			Ok(<Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, to).saturating_sub(before))
+		})?;
+		Self::record_transfer_volume(currency_id, from, actual);
+		Self::index_transfer(currency_id, from, to, actual);
+		#[cfg(feature = "metrics")]
+		T::Metrics::on_transfer(currency_id);
+		Self::deposit_event(Event::Transferred(
+			currency_id,
+			from.clone(),
+			to.clone(),
+			actual,
+			Self::bump_operation_nonce(from),
+			Self::bump_operation_nonce(to),
+		));
+		Self::auto_sweep(currency_id, to, actual);
+		Ok(actual)
+	}
+
+	/// The offchain index key of the transfer record of `who` for the event
+	/// at `event_index` of block `now`.
+	pub fn transfer_index_key(who: &T::AccountId, now: T::BlockNumber, event_index: u32) -> Vec<u8> {
+		(TRANSFER_INDEX_PREFIX, who, now, event_index).encode()
+	}
+
+	/// Write a `(block, counterparty, currency, amount)` record of the
+	/// transfer for both `from` and `to`, if the node enables offchain
+	/// indexing.
+	fn index_transfer(
+		currency_id: CurrencyIdOf<T, I>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T, I>,
+	) {
+		let now = <frame_system::Module<T>>::block_number();
+		// The index of the `Transferred` event deposited next.
+		let event_index = <frame_system::Module<T>>::event_count();
+		for (who, counterparty) in [(from, to), (to, from)].iter() {
+			sp_io::offchain_index::set(
+				&Self::transfer_index_key(who, now, event_index),
+				&(now, counterparty, currency_id, amount).encode(),
+			);
+		}
+	}
+
+	/// The free balance of `who` under `currency_id` minus the largest lock
+	/// or freeze set through this pallet.
+	pub fn usable_balance(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> BalanceOf<T, I> {
+		<Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, who)
+			.saturating_sub(Self::max_lock(currency_id, who).max(Self::balance_frozen(currency_id, who)))
+	}
+
+	/// The balance of `who` under `currency_id` restricted by freezes, the
+	/// largest of them as freezes overlap.
+	pub fn balance_frozen(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> BalanceOf<T, I> {
+		Self::freezes(who, currency_id)
+			.iter()
+			.map(|freeze| freeze.amount)
+			.max()
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Freeze `amount` of `currency_id` on `who` under `freeze_id`,
+	/// replacing any freeze with the same id.
+	///
+	/// Freezes are kept apart from locks, so restrictions such as
+	/// governance voting do not replace or get replaced by vesting locks.
+	pub fn set_freeze(
+		freeze_id: LockIdentifier,
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		amount: BalanceOf<T, I>,
+	) -> DispatchResult {
+		let mut freezes = Self::freezes(who, currency_id);
+		match freezes.iter_mut().find(|freeze| freeze.id == freeze_id) {
+			Some(freeze) => freeze.amount = amount,
+			None => freezes.push(BalanceLock { id: freeze_id, amount }),
+		}
+		Self::apply_freezes(currency_id, who, freezes)?;
+		Self::deposit_event(Event::Frozen(currency_id, who.clone(), freeze_id, amount));
+		Ok(())
+	}
+
+	/// Remove the freeze `freeze_id` of `currency_id` on `who`.
+	pub fn thaw(freeze_id: LockIdentifier, currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> DispatchResult {
+		let mut freezes = Self::freezes(who, currency_id);
+		freezes.retain(|freeze| freeze.id != freeze_id);
+		Self::apply_freezes(currency_id, who, freezes)?;
+		Self::deposit_event(Event::Thawed(currency_id, who.clone(), freeze_id));
+		Ok(())
+	}
+
+	/// Store `freezes` and enforce the largest of them on the backing
+	/// currency under `FREEZE_LOCK_ID`.
+	fn apply_freezes(
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		freezes: Vec<BalanceLock<BalanceOf<T, I>>>,
+	) -> DispatchResult {
+		let frozen = freezes.iter().map(|freeze| freeze.amount).max();
+		match frozen {
+			Some(amount) if currency_id == Self::native_currency_id() => {
+				T::Stp258Native::set_lock(FREEZE_LOCK_ID, who, amount)?
+			}
+			Some(amount) => T::Stp258Currency::set_lock(FREEZE_LOCK_ID, currency_id, who, amount)?,
+			None if currency_id == Self::native_currency_id() => T::Stp258Native::remove_lock(FREEZE_LOCK_ID, who)?,
+			None => T::Stp258Currency::remove_lock(FREEZE_LOCK_ID, currency_id, who)?,
+		}
+		if freezes.is_empty() {
+			Freezes::<T, I>::remove(who, currency_id);
+		} else {
+			Freezes::<T, I>::insert(who, currency_id, freezes);
+		}
+		Ok(())
+	}
+
+	/// Check whether `amount` of `currency_id` can be withdrawn from `who`,
+	/// and why not.
+	pub fn can_withdraw(
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		amount: BalanceOf<T, I>,
+	) -> WithdrawConsequence<BalanceOf<T, I>> {
+		let free = <Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, who);
+		if amount.is_zero() {
+			return WithdrawConsequence::Success(free);
+		}
+		if amount > <Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id) {
+			return WithdrawConsequence::Underflow;
+		}
+		let remaining = match free.checked_sub(&amount) {
+			Some(remaining) => remaining,
+			None => return WithdrawConsequence::NoFunds,
+		};
+		if remaining < Self::max_lock(currency_id, who).max(Self::balance_frozen(currency_id, who))
+			|| <Self as Stp258Currency<T::AccountId>>::ensure_can_withdraw(currency_id, who, amount).is_err()
+		{
+			return WithdrawConsequence::Frozen;
+		}
+		let reserved = <Self as Stp258CurrencyReservable<T::AccountId>>::reserved_balance(currency_id, who);
+		if remaining.saturating_add(reserved) < <Self as Stp258Currency<T::AccountId>>::minimum_balance(currency_id) {
+			return WithdrawConsequence::WouldDie;
+		}
+		WithdrawConsequence::Success(remaining)
+	}
+
+	/// The locks set through this pallet on `who` under `currency_id`,
+	/// largest first, and the id of the binding lock restricting the
+	/// balance, if any.
+	pub fn locks_by_amount(
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+	) -> (Vec<BalanceLock<BalanceOf<T, I>>>, Option<LockIdentifier>) {
+		let mut locks = Self::locks(who, currency_id);
+		locks.sort_by(|a, b| b.amount.cmp(&a.amount));
+		let binding = locks.first().map(|lock| lock.id);
+		(locks, binding)
+	}
+
+	/// The number of locks set through this pallet on `who` under
+	/// `currency_id`.
+	pub fn lock_count(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> u32 {
+		Self::locks(who, currency_id).len() as u32
+	}
+
+	/// Ensure `lock_id` is already set on `who` under `currency_id`, or
+	/// another lock fits within `MaxLocks`.
+	fn ensure_can_add_lock(lock_id: LockIdentifier, currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> DispatchResult {
+		let locks = Self::locks(who, currency_id);
+		ensure!(
+			locks.iter().any(|lock| lock.id == lock_id) || (locks.len() as u32) < T::MaxLocks::get(),
+			Error::<T, I>::TooManyLocks
+		);
+		Ok(())
+	}
+
+	/// The largest lock set through this pallet on `who` under
+	/// `currency_id`.
+	fn max_lock(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> BalanceOf<T, I> {
+		Self::locks(who, currency_id)
+			.iter()
+			.map(|lock| lock.amount)
+			.max()
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Append a supply change of `currency_id` to its history, dropping the
+	/// oldest entries beyond `MaxSupplyHistory`.
+	fn record_supply_change(
+		currency_id: CurrencyIdOf<T, I>,
+		amount: AmountOf<T, I>,
+		source: SupplyChangeSource<T::Hash>,
+	) {
+		SupplyHistory::<T, I>::mutate(currency_id, |history| {
+			history.push(SupplyChange {
+				block: <frame_system::Module<T>>::block_number(),
+				amount,
+				source,
+			});
+			let max = T::MaxSupplyHistory::get() as usize;
+			if history.len() > max {
+				let excess = history.len() - max;
+				history.drain(..excess);
+			}
+		});
+	}
+
+	/// Count a balance operation of `who`, returning its nonce.
+	///
+	/// Transfers, deposits, withdrawals, balance updates and slashes are
+	/// counted, so an indexer seeing a gap in an account's nonces has missed
+	/// one of their events.
+	fn bump_operation_nonce(who: &T::AccountId) -> u64 {
+		OperationNonces::<T, I>::mutate(who, |nonce| {
+			*nonce = nonce.saturating_add(1);
+			*nonce
+		})
+	}
+
+	/// The message the payer of `authorization` signs for this chain and
+	/// instance.
+	pub fn authorization_payload(authorization: &TransferAuthorizationOf<T, I>) -> Vec<u8> {
+		let genesis_hash = <frame_system::Module<T>>::block_hash(T::BlockNumber::zero());
+		authorization.signing_payload(&genesis_hash, &T::ModuleId::get())
+	}
+
+	/// The deposit account of `parent` for `tag`.
+	///
+	/// This is `derive_sub_account(ModuleId, hash)` with `hash` the
+	/// `T::Hashing` hash of `(b"deposit", parent, tag)`.
+	pub fn deposit_account_of(parent: &T::AccountId, tag: u32) -> T::AccountId {
+		derive_sub_account::<T::AccountId, T::Hashing>(
+			T::ModuleId::get(),
+			T::Hashing::hash_of(&(b"deposit", parent, tag)),
+		)
+	}
+
+	/// Forward `amount` of `currency_id` just credited to `who` to its
+	/// parent, if `who` is an auto-sweeping deposit account.
+	fn auto_sweep(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId, amount: BalanceOf<T, I>) {
+		if currency_id == Self::native_currency_id() {
+			return;
+		}
+		let parent = match Self::deposit_account(who) {
+			Some(DepositAccount {
+				parent,
+				auto_sweep: true,
+				..
+			}) => parent,
+			_ => return,
+		};
+		match Self::transfer_returning_actual(currency_id, who, &parent, amount) {
+			Ok(swept) => Self::deposit_event(Event::DepositAccountSwept(currency_id, who.clone(), swept)),
+			Err(e) => log::warn!(
+				target: LOG_TARGET,
+				"sweeping {:?} of {:?} under {:?} failed: {:?}",
+				amount,
+				who,
+				currency_id,
+				e,
+			),
+		}
+	}
+
+	/// The account of `sub_account`, derived from `ModuleId`.
+	pub fn sub_account(sub_account: SubAccount) -> T::AccountId {
+		derive_sub_account::<T::AccountId, T::Hashing>(T::ModuleId::get(), sub_account)
+	}
+
+	/// Register `sub_account` as active, returning its account.
+	pub fn register_sub_account(sub_account: SubAccount) -> result::Result<T::AccountId, DispatchError> {
+		ensure!(
+			!SubAccounts::<T, I>::contains_key(sub_account),
+			Error::<T, I>::SubAccountAlreadyRegistered
+		);
+		let account = Self::sub_account(sub_account);
+		SubAccounts::<T, I>::insert(sub_account, account.clone());
+		Self::deposit_event(Event::SubAccountRegistered(sub_account, account.clone()));
+		Ok(account)
+	}
+
+	/// Deregister `sub_account` once it is no longer used.
+	///
+	/// The caller should empty it first, funds left in it are orphaned.
+	pub fn deregister_sub_account(sub_account: SubAccount) -> DispatchResult {
+		SubAccounts::<T, I>::take(sub_account).ok_or(Error::<T, I>::UnknownSubAccount)?;
+		Self::deposit_event(Event::SubAccountDeregistered(sub_account));
+		Ok(())
+	}
+
+	/// The active protocol sub-accounts and their accounts.
+	pub fn sub_accounts() -> Vec<(SubAccount, T::AccountId)> {
+		SubAccounts::<T, I>::iter().collect()
+	}
+
+	/// The total issuance of `currency_id` minus the balances of the
+	/// registered sub-accounts and the balance locked through this pallet.
+	pub fn circulating_supply(currency_id: CurrencyIdOf<T, I>) -> BalanceOf<T, I> {
+		let (protocol, protocol_locked) = SubAccounts::<T, I>::iter_values().fold(
+			(Zero::zero(), Zero::zero()),
+			|(total, locked): (BalanceOf<T, I>, BalanceOf<T, I>), account| {
+				(
+					total.saturating_add(<Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, &account)),
+					locked.saturating_add(Self::max_lock(currency_id, &account)),
+				)
+			},
+		);
+		// locks on sub-accounts are already excluded with their balance
+		let locked = Self::total_locked(currency_id).saturating_sub(protocol_locked);
+		<Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id)
+			.saturating_sub(protocol)
+			.saturating_sub(locked)
+	}
+
+	/// The account dust of `currency_id` is aggregated in.
+	pub fn dust_account(currency_id: CurrencyIdOf<T, I>) -> T::AccountId {
+		derive_sub_account::<T::AccountId, T::Hashing>(T::DustModuleId::get(), currency_id)
+	}
+
+	/// Send the dust aggregated during the era ending at `now` to
+	/// `DustDestination`, returning the weight used.
+	///
+	/// A pass starts at each era boundary and sends at most
+	/// `MaxDustRedistributionsPerBlock` currencies per block. Dust that fails
+	/// to be sent stays aggregated and is retried in the next era's pass.
+	fn redistribute_dust(now: T::BlockNumber) -> Weight {
+		let era_length = T::EraLength::get();
+		if !era_length.is_zero() && (now % era_length).is_zero() {
+			// a pass still under way from the last era starts over
+			DustRedistributionPass::<T, I>::put(0);
+		}
+		let failed = match Self::dust_redistribution_pass() {
+			Some(failed) => failed,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let limit = T::MaxDustRedistributionsPerBlock::get();
+		// the dust that failed this pass sorts before what is left
+		let batch: Vec<_> = AccumulatedDust::<T, I>::iter()
+			.skip(failed as usize)
+			.take(limit as usize)
+			.collect();
+		let count = batch.len() as u32;
+		let destination = T::DustDestination::get();
+		let mut failures = 0u32;
+		for (currency_id, amount) in batch {
+			let dust_account = Self::dust_account(currency_id);
+			let sent = match destination {
+				DustDestination::Treasury => <Self as Stp258Currency<T::AccountId>>::transfer(
+					currency_id,
+					&dust_account,
+					&T::TreasuryAccount::get(),
+					amount,
+				),
+				DustDestination::Burn => {
+					<Self as Stp258Currency<T::AccountId>>::withdraw(currency_id, &dust_account, amount)
+				}
+			};
+			match sent {
+				Ok(()) => {
+					AccumulatedDust::<T, I>::remove(currency_id);
+					Self::deposit_event(Event::DustRedistributed(currency_id, amount, destination));
+				}
+				Err(e) => {
+					failures += 1;
+					log::warn!(
+						target: LOG_TARGET,
+						"redistributing {:?} dust of {:?} failed, kept for the next era: {:?}",
+						amount,
+						currency_id,
+						e,
+					);
+				}
+			}
+		}
+		if count == 0 || count < limit {
+			DustRedistributionPass::<T, I>::kill();
+		} else {
+			DustRedistributionPass::<T, I>::put(failed.saturating_add(failures));
+		}
+		T::WeightInfo::redistribute_dust(count.saturating_add(failed))
+	}
+
+	/// Sample the total issuance of the `SampledCurrencies` due at `now`,
+	/// returning the weight used.
+	///
+	/// Every sampled currency is due at era boundaries, and the non-native
+	/// ones at SERP adjustment points too.
+	fn sample_issuance(now: T::BlockNumber) -> Weight {
+		let at_boundary = |length: T::BlockNumber| !length.is_zero() && (now % length).is_zero();
+		let era_boundary = at_boundary(T::EraLength::get());
+		let adjustment_point = match Self::serp_parameter(SerpParameterKey::AdjustmentFrequency) {
+			Some(SerpParameter::AdjustmentFrequency(frequency)) => at_boundary(frequency),
+			_ => false,
+		};
+		if !era_boundary && !adjustment_point {
+			return 0;
+		}
+		let native_currency_id = Self::native_currency_id();
+		let mut sampled = 0;
+		for currency_id in T::SampledCurrencies::get() {
+			if !era_boundary && currency_id == native_currency_id {
+				continue;
+			}
+			let total_issuance = <Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id);
+			IssuanceHistory::<T, I>::mutate(currency_id, |history| {
+				history.push((now, total_issuance));
+				let excess = history.len().saturating_sub(T::MaxIssuanceSamples::get() as usize);
+				history.drain(..excess);
+			});
+			sampled += 1;
+		}
+		T::WeightInfo::sample_issuance(sampled)
+	}
+
+	/// Issue the native currency of the era starting at `now` to the
+	/// issuance beneficiaries, returning the weight used.
+	fn issue_native(now: T::BlockNumber) -> Weight {
+		let era_length = T::EraLength::get();
+		if era_length.is_zero() || !(now % era_length).is_zero() {
+			return 0;
+		}
+		let schedule = match Self::issuance_schedule() {
+			Some(schedule) => schedule,
+			None => return T::DbWeight::get().reads(1),
+		};
+
+		let native_currency_id = Self::native_currency_id();
+		let era_issuance = Self::next_era_issuance();
+		let beneficiaries = Self::issuance_beneficiaries();
+		let mut issued: BalanceOf<T, I> = Zero::zero();
+		for (beneficiary, share) in &beneficiaries {
+			let amount = *share * era_issuance;
+			match <Self as Stp258Currency<T::AccountId>>::deposit(native_currency_id, beneficiary, amount) {
+				Ok(()) => issued = issued.saturating_add(amount),
+				Err(e) => log::warn!(
+					target: LOG_TARGET,
+					"issuing {:?} to {:?} failed: {:?}",
+					amount,
+					beneficiary,
+					e,
+				),
+			}
+		}
 
-pub use module::*;
+		if let IssuanceSchedule::Decaying { decay, .. } = schedule {
+			NextEraIssuance::<T, I>::put(decay.left_from_one() * era_issuance);
+		}
+		Self::record_supply_change(
+			native_currency_id,
+			conversions::balance_to_amount_saturating(issued),
+			SupplyChangeSource::Issuance,
+		);
+		Self::deposit_event(Event::NativeIssued(issued));
+		T::WeightInfo::issue_native(beneficiaries.len() as u32)
+	}
 
-#[frame_support::pallet]
-pub mod module {
-	use super::*;
+	/// Withdraw `amount` of `currency_id` from `who` as fee revenue,
+	/// shared out according to the fee split.
+	///
+	/// Goes through this pallet's own `withdraw` and `deposit`, so fees
+	/// emit the same events, bump the same nonces and reach the same
+	/// observers as any other balance change.
+	fn collect_fee(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId, amount: BalanceOf<T, I>) -> DispatchResult {
+		<Self as Stp258Currency<T::AccountId>>::withdraw(currency_id, who, amount)?;
 
-	pub trait WeightInfo {
-		fn transfer_non_native_currency() -> Weight;
-		fn transfer_native_currency() -> Weight;
-		fn update_balance_non_native_currency() -> Weight;
-		fn update_balance_native_currency_creating() -> Weight;
-		fn update_balance_native_currency_killing() -> Weight;
+		let fee_split = Self::fee_split();
+		for (beneficiary, share) in [
+			(T::TreasuryAccount::get(), fee_split.treasury),
+			(T::SerperAccount::get(), fee_split.serpers),
+		]
+		.iter()
+		{
+			let credit = *share * amount;
+			if credit.is_zero() {
+				continue;
+			}
+			if let Err(e) = <Self as Stp258Currency<T::AccountId>>::deposit(currency_id, beneficiary, credit) {
+				log::warn!(
+					target: LOG_TARGET,
+					"fee share {:?} under {:?} burned, crediting {:?} failed: {:?}",
+					credit,
+					currency_id,
+					beneficiary,
+					e,
+				);
+			}
+		}
+
+		let era = Self::era_of(<frame_system::Module<T>>::block_number());
+		FeeRevenue::<T, I>::mutate(era, currency_id, |revenue| *revenue = revenue.saturating_add(amount));
+		Ok(())
 	}
 
-	pub(crate) type BalanceOf<T> =
-		<<T as Config>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::Balance;
-	pub(crate) type CurrencyIdOf<T> =
-		<<T as Config>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::CurrencyId;
-	pub(crate) type AmountOf<T> =
-		<<T as Config>::Stp258Currency as Stp258CurrencyExtended<<T as frame_system::Config>::AccountId>>::Amount;
+	/// Drop the fee revenue of the era leaving the retention window as the
+	/// era starting at `now` enters it, returning the weight used.
+	fn prune_fee_revenue(now: T::BlockNumber) -> Weight {
+		let era_length = T::EraLength::get();
+		if era_length.is_zero() || !(now % era_length).is_zero() {
+			return 0;
+		}
+		match Self::era_of(now).checked_sub(T::FeeRevenueRetention::get()) {
+			Some(expired) => {
+				FeeRevenue::<T, I>::remove_prefix(expired);
+				T::WeightInfo::prune_fee_revenue()
+			}
+			None => 0,
+		}
+	}
 
-	#[pallet::config]
-	pub trait Config: frame_system::Config {
-		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	/// Charge the demurrage `who` owes on `currency_id` since it was last
+	/// charged, as fee revenue.
+	///
+	/// Demurrage accrues linearly on the usable balance and is charged when
+	/// the balance is next touched by a transfer, deposit or withdrawal, in
+	/// the same storage transaction so a failed operation charges nothing.
+	/// Locked funds are not charged, and the treasury is exempt.
+	fn charge_demurrage(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) {
+		let rate = match Self::demurrage_rate(currency_id) {
+			Some(rate) => rate,
+			None => return,
+		};
+		if *who == T::TreasuryAccount::get() {
+			return;
+		}
+		let now = <frame_system::Module<T>>::block_number();
+		let last = match AccountActivities::<T, I>::mutate(who, currency_id, |activity| {
+			activity.demurrage_charged_at.replace(now)
+		}) {
+			Some(last) => last,
+			None => return,
+		};
 
-		type Stp258Currency: MergeAccount<Self::AccountId>
-			+ Stp258CurrencyExtended<Self::AccountId>
-			+ Stp258CurrencyLockable<Self::AccountId>
-			+ Stp258CurrencyReservable<Self::AccountId>;
+		let elapsed: u32 = now.saturating_sub(last).saturated_into();
+		let owed = Perbill::from_parts(rate.deconstruct().saturating_mul(elapsed))
+			* Self::usable_balance(currency_id, who);
+		if owed.is_zero() {
+			return;
+		}
+		match Self::collect_fee(currency_id, who, owed) {
+			Ok(()) => Self::deposit_event(Event::DemurrageCharged(currency_id, who.clone(), owed)),
+			Err(e) => log::warn!(
+				target: LOG_TARGET,
+				"charging {:?} demurrage of {:?} under {:?} failed: {:?}",
+				owed,
+				who,
+				currency_id,
+				e,
+			),
+		}
+	}
 
-		type Stp258Native: Stp258AssetExtended<Self::AccountId, Balance = BalanceOf<Self>, Amount = AmountOf<Self>>
-			+ Stp258AssetLockable<Self::AccountId, Balance = BalanceOf<Self>>
-			+ Stp258AssetReservable<Self::AccountId, Balance = BalanceOf<Self>>;
+	/// The savings of `who` in `currency_id`, interest included.
+	pub fn savings_balance(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> BalanceOf<T, I> {
+		Self::current_savings_index(currency_id)
+			.saturating_mul_int(Self::savings(who, currency_id).shares.saturated_into::<u128>())
+			.saturated_into()
+	}
 
-		#[pallet::constant]
-		type GetStp258NativeId: Get<CurrencyIdOf<Self>>;
+	/// The savings index of `currency_id` accrued up to the current block.
+	fn current_savings_index(currency_id: CurrencyIdOf<T, I>) -> FixedU128 {
+		let now = <frame_system::Module<T>>::block_number();
+		let (index, last) = Self::savings_index(currency_id).unwrap_or_else(|| (FixedU128::one(), now));
+		let elapsed: u128 = now.saturating_sub(last).saturated_into();
+		let growth = FixedU128::saturating_from_rational(
+			(Self::savings_rate(currency_id).deconstruct() as u128).saturating_mul(elapsed),
+			Perbill::one().deconstruct() as u128,
+		);
+		index.saturating_mul(FixedU128::one().saturating_add(growth))
+	}
 
-		/// Weight information for extrinsics in this module.
-		type WeightInfo: WeightInfo;
+	/// Accrue the savings index of `currency_id` up to the current block.
+	fn accrue_savings_index(currency_id: CurrencyIdOf<T, I>) -> FixedU128 {
+		let index = Self::current_savings_index(currency_id);
+		SavingsIndices::<T, I>::insert(currency_id, (index, <frame_system::Module<T>>::block_number()));
+		index
 	}
 
-	#[pallet::error]
-	pub enum Error<T> {
-		/// Unable to convert the Amount type into Balance.
-		AmountIntoBalanceFailed,
-		/// Balance is too low.
-		BalanceTooLow,
+	/// The savings shares worth `amount` at `index`.
+	fn savings_shares(index: FixedU128, amount: BalanceOf<T, I>) -> BalanceOf<T, I> {
+		index
+			.reciprocal()
+			.unwrap_or_else(Zero::zero)
+			.saturating_mul_int(amount.saturated_into::<u128>())
+			.saturated_into()
 	}
 
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// Currency transfer success. [currency_id, from, to, amount]
-		Transferred(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>),
-		/// Update balance success. [currency_id, who, amount]
-		BalanceUpdated(CurrencyIdOf<T>, T::AccountId, AmountOf<T>),
-		/// Deposit success. [currency_id, who, amount]
-		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
-		/// Withdraw success. [currency_id, who, amount]
-		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+	/// The index of the era `now` is in.
+	pub(crate) fn era_of(now: T::BlockNumber) -> u32 {
+		let era_length = T::EraLength::get();
+		if era_length.is_zero() {
+			return 0;
+		}
+		(now / era_length).saturated_into()
 	}
 
-	#[pallet::pallet]
-	pub struct Pallet<T>(PhantomData<T>);
+	/// Add `amount` to the volume of `currency_id` transferred by `who` in
+	/// the current era, if `currency_id` is tracked.
+	fn record_transfer_volume(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId, amount: BalanceOf<T, I>) {
+		if amount.is_zero() || !Self::volume_tracked(currency_id) {
+			return;
+		}
+		let era = Self::era_of(<frame_system::Module<T>>::block_number());
+		AccountActivities::<T, I>::mutate(who, currency_id, |activity| {
+			activity.transfer_volume = Some(match activity.transfer_volume.take() {
+				Some((recorded, total)) if recorded == era => (era, total.saturating_add(amount)),
+				_ => (era, amount),
+			})
+		});
+	}
 
-	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	/// Set a lock on `who` under `currency_id` that is removed at block
+	/// `until`.
+	pub fn set_lock_until(
+		lock_id: LockIdentifier,
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		amount: BalanceOf<T, I>,
+		until: T::BlockNumber,
+	) -> DispatchResult {
+		ensure!(
+			until > <frame_system::Module<T>>::block_number(),
+			Error::<T, I>::InvalidLockExpiry
+		);
+		ensure!(
+			(Self::lock_expiry_queue(until).len() as u32) < T::MaxLockExpiriesPerBlock::get(),
+			Error::<T, I>::TooManyLockExpiries
+		);
+		<Self as Stp258CurrencyLockable<T::AccountId>>::set_lock(lock_id, currency_id, who, amount)?;
+		LockExpiry::<T, I>::insert(who, (currency_id, lock_id), until);
+		LockExpiryQueue::<T, I>::mutate(until, |queue| queue.push((who.clone(), currency_id, lock_id)));
+		Ok(())
+	}
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Transfer some balance to another account under `currency_id`.
-		///
-		/// The dispatch origin for this call must be `Signed` by the
-		/// transactor.
-		#[pallet::weight(T::WeightInfo::transfer_non_native_currency())]
-		pub fn transfer(
-			origin: OriginFor<T>,
-			dest: <T::Lookup as StaticLookup>::Source,
-			currency_id: CurrencyIdOf<T>,
-			#[pallet::compact] amount: BalanceOf<T>,
-		) -> DispatchResultWithPostInfo {
-			let from = ensure_signed(origin)?;
-			let to = T::Lookup::lookup(dest)?;
-			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
-			Ok(().into())
+	/// Hold `amount` of `currency_id` on `who` for
+	/// `ReserveExpiryHoldReason` and release it at block `until`.
+	pub fn reserve_until(
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		amount: BalanceOf<T, I>,
+		until: T::BlockNumber,
+	) -> DispatchResult {
+		ensure!(
+			until > <frame_system::Module<T>>::block_number(),
+			Error::<T, I>::InvalidReserveExpiry
+		);
+		ensure!(
+			(Self::reserve_expiry_queue(until).len() as u32) < T::MaxReserveExpiriesPerBlock::get(),
+			Error::<T, I>::TooManyReserveExpiries
+		);
+		Self::hold(T::ReserveExpiryHoldReason::get(), currency_id, who, amount)?;
+		ReserveExpiryQueue::<T, I>::mutate(until, |queue| queue.push((who.clone(), currency_id, amount)));
+		Ok(())
+	}
+
+	/// Release the reserves scheduled for block `now`, returning the weight
+	/// used.
+	fn expire_reserves(now: T::BlockNumber) -> Weight {
+		let expiring = ReserveExpiryQueue::<T, I>::take(now);
+		let count = expiring.len() as u32;
+		for (who, currency_id, amount) in expiring {
+			// part of the hold may have been released since
+			let released = Self::release(T::ReserveExpiryHoldReason::get(), currency_id, &who, amount);
+			Self::deposit_event(Event::ReserveExpired(currency_id, who, released));
 		}
+		T::WeightInfo::expire_reserves(count)
+	}
 
-		/// Transfer some native currency to another account.
-		///
-		/// The dispatch origin for this call must be `Signed` by the
-		/// transactor.
-		#[pallet::weight(T::WeightInfo::transfer_native_currency())]
-		pub fn transfer_native_currency(
-			origin: OriginFor<T>,
-			dest: <T::Lookup as StaticLookup>::Source,
-			#[pallet::compact] amount: BalanceOf<T>,
-		) -> DispatchResultWithPostInfo {
-			let from = ensure_signed(origin)?;
-			let to = T::Lookup::lookup(dest)?;
-			T::Stp258Native::transfer(&from, &to, amount)?;
+	/// Ensure a namespaced `lock_id` uses a registered prefix.
+	fn ensure_lock_id_registered(lock_id: &LockIdentifier) -> DispatchResult {
+		if let Some(prefix) = lock_id::prefix_of(lock_id) {
+			ensure!(
+				Self::lock_prefix_registered(prefix),
+				Error::<T, I>::UnregisteredLockPrefix
+			);
+		}
+		Ok(())
+	}
 
-			Self::deposit_event(Event::Transferred(T::GetStp258NativeId::get(), from, to, amount));
-			Ok(().into())
+	/// Record the lock `lock_id` of `who` under `currency_id`, `None`
+	/// removes it.
+	fn track_lock(
+		lock_id: LockIdentifier,
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		amount: Option<BalanceOf<T, I>>,
+	) {
+		let old_max_lock = Self::max_lock(currency_id, who);
+		let mut locks = Self::locks(who, currency_id);
+		locks.retain(|lock| lock.id != lock_id);
+		if let Some(amount) = amount {
+			locks.push(BalanceLock { id: lock_id, amount });
+		}
+		if locks.is_empty() {
+			Locks::<T, I>::remove(who, currency_id);
+		} else {
+			Locks::<T, I>::insert(who, currency_id, locks);
 		}
+		let new_max_lock = Self::max_lock(currency_id, who);
+		TotalLocked::<T, I>::mutate(currency_id, |total| {
+			*total = total.saturating_add(new_max_lock).saturating_sub(old_max_lock)
+		});
+	}
 
-		/// update amount of account `who` under `currency_id`.
-		///
-		/// The dispatch origin of this call must be _Root_.
-		#[pallet::weight(T::WeightInfo::update_balance_non_native_currency())]
-		pub fn update_balance(
-			origin: OriginFor<T>,
-			who: <T::Lookup as StaticLookup>::Source,
-			currency_id: CurrencyIdOf<T>,
-			amount: AmountOf<T>,
-		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			let dest = T::Lookup::lookup(who)?;
-			<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
-			Ok(().into())
+	/// The free balances of `accounts` under `currency_id`, in order.
+	///
+	/// Only the first `MaxBalancesPerQuery` accounts are answered for.
+	pub fn balances_of(currency_id: CurrencyIdOf<T, I>, accounts: &[T::AccountId]) -> Vec<BalanceOf<T, I>> {
+		accounts
+			.iter()
+			.take(T::MaxBalancesPerQuery::get() as usize)
+			.map(|who| <Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, who))
+			.collect()
+	}
+
+	/// The total balance of `who` under `currency_id` at the end of `block`.
+	///
+	/// Returns `None` if the currency is not checkpointed, `block` precedes
+	/// checkpointing, or its checkpoint has been dropped. Only changes made
+	/// through this pallet are checkpointed.
+	pub fn balance_at(
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		block: T::BlockNumber,
+	) -> Option<BalanceOf<T, I>> {
+		let since = Self::checkpointed_since(currency_id)?;
+		if block < since {
+			return None;
+		}
+		let checkpoints = Self::checkpoints_of(currency_id, who);
+		if checkpoints.is_empty() {
+			return Some(<Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, who));
+		}
+		match checkpoints.binary_search_by(|(at, _)| at.cmp(&block)) {
+			Ok(i) => Some(checkpoints[i].1),
+			Err(0) => None,
+			Err(i) => Some(checkpoints[i - 1].1),
+		}
+	}
+
+	/// Run `f` and checkpoint the total balances of `accounts` it changed,
+	/// if `currency_id` is checkpointed.
+	fn track_checkpoints<R>(currency_id: CurrencyIdOf<T, I>, accounts: &[&T::AccountId], f: impl FnOnce() -> R) -> R {
+		let since = match Self::checkpointed_since(currency_id) {
+			Some(since) => since,
+			None => return f(),
+		};
+		let total_balance = |who| <Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, who);
+		let before: Vec<BalanceOf<T, I>> = accounts.iter().map(|who| total_balance(*who)).collect();
+		let result = f();
+		let now = <frame_system::Module<T>>::block_number();
+		for (who, before) in accounts.iter().zip(before) {
+			let after = total_balance(*who);
+			if after == before {
+				continue;
+			}
+			let mut checkpoints = Self::checkpoints_of(currency_id, who);
+			// the balance held since checkpointing began, as first seen
+			if checkpoints.is_empty() {
+				checkpoints.push((since, before));
+			}
+			match checkpoints.last_mut() {
+				Some((at, balance)) if *at == now => *balance = after,
+				_ => checkpoints.push((now, after)),
+			}
+			let excess = checkpoints.len().saturating_sub(T::MaxCheckpoints::get() as usize);
+			checkpoints.drain(..excess);
+			if Self::child_trie_checkpointing(currency_id) {
+				who.using_encoded(|key| child::put(&Self::checkpoints_child_info(currency_id), key, &checkpoints));
+			} else {
+				BalanceCheckpoints::<T, I>::insert(*who, currency_id, checkpoints);
+			}
+		}
+		result
+	}
+
+	/// Start checkpointing `currency_id` from the current block.
+	fn do_enable_checkpointing(currency_id: CurrencyIdOf<T, I>) -> DispatchResult {
+		ensure!(
+			!CheckpointedSince::<T, I>::contains_key(currency_id),
+			Error::<T, I>::CheckpointingAlreadyEnabled
+		);
+		ensure!(
+			!Self::child_trie_checkpointing(currency_id),
+			Error::<T, I>::CheckpointPruneInProgress
+		);
+		CheckpointedSince::<T, I>::insert(currency_id, <frame_system::Module<T>>::block_number());
+		Self::deposit_event(Event::CheckpointingEnabled(currency_id));
+		Ok(())
+	}
+
+	/// The child trie holding the checkpoints of `currency_id` under
+	/// `enable_child_trie_checkpointing`, keyed by encoded account id.
+	///
+	/// Derived from this instance's storage prefix, so instances sharing
+	/// currency ids do not share tries.
+	pub fn checkpoints_child_info(currency_id: CurrencyIdOf<T, I>) -> ChildInfo {
+		ChildInfo::new_default(&(CheckpointedSince::<T, I>::final_prefix(), currency_id).encode())
+	}
+
+	/// The balance checkpoints of `who` under `currency_id`, oldest first,
+	/// from wherever the currency keeps them.
+	pub fn checkpoints_of(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> Vec<(T::BlockNumber, BalanceOf<T, I>)> {
+		if Self::child_trie_checkpointing(currency_id) {
+			who.using_encoded(|key| child::get_or_default(&Self::checkpoints_child_info(currency_id), key))
+		} else {
+			Self::balance_checkpoints(who, currency_id)
+		}
+	}
+
+	/// Run `f` and record the change it made to the reserved balances of
+	/// `accounts` under `currency_id` in `TotalReserved`.
+	fn track_reserved<R>(currency_id: CurrencyIdOf<T, I>, accounts: &[&T::AccountId], f: impl FnOnce() -> R) -> R {
+		let reserved = || {
+			accounts.iter().fold(Zero::zero(), |total: BalanceOf<T, I>, who| {
+				total.saturating_add(<Self as Stp258CurrencyReservable<T::AccountId>>::reserved_balance(
+					currency_id,
+					who,
+				))
+			})
+		};
+		let before = reserved();
+		let result = f();
+		let after = reserved();
+		TotalReserved::<T, I>::mutate(currency_id, |total| {
+			*total = total.saturating_add(after).saturating_sub(before)
+		});
+		result
+	}
+
+	/// Move up to `amount` of the reserved balance of `who` under
+	/// `currency_id` to the free balance of `beneficiary` in one step,
+	/// returning the amount moved.
+	///
+	/// Balance held for a reason through `HoldableCurrency` is not moved.
+	pub fn unreserve_to(
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		beneficiary: &T::AccountId,
+		amount: BalanceOf<T, I>,
+	) -> result::Result<BalanceOf<T, I>, DispatchError> {
+		let unheld = Self::unheld_reserved_balance(currency_id, who).min(amount);
+		if unheld.is_zero() {
+			return Ok(Zero::zero());
+		}
+		let remaining = <Self as Stp258CurrencyReservable<T::AccountId>>::repatriate_reserved(
+			currency_id,
+			who,
+			beneficiary,
+			unheld,
+			BalanceStatus::Free,
+		)?;
+		let moved = unheld.saturating_sub(remaining);
+		Self::deposit_event(Event::UnreservedTo(currency_id, who.clone(), beneficiary.clone(), moved));
+		Ok(moved)
+	}
+
+	/// Move reserved balance of `slashed` under `currency_id` to each
+	/// `(beneficiary, amount, status)` in turn, all or nothing.
+	///
+	/// Fails with `InsufficientReservedBalance`, moving nothing, if any
+	/// amount cannot be repatriated in full. Balance held for a reason
+	/// through `HoldableCurrency` is not moved.
+	pub fn repatriate_reserved_multi(
+		currency_id: CurrencyIdOf<T, I>,
+		slashed: &T::AccountId,
+		beneficiaries: Vec<(T::AccountId, BalanceOf<T, I>, BalanceStatus)>,
+	) -> DispatchResult {
+		with_transaction_result(|| {
+			for (beneficiary, amount, status) in beneficiaries {
+				let remaining = <Self as Stp258CurrencyReservable<T::AccountId>>::repatriate_reserved(
+					currency_id,
+					slashed,
+					&beneficiary,
+					amount,
+					status,
+				)?;
+				ensure!(remaining.is_zero(), Error::<T, I>::InsufficientReservedBalance);
+			}
+			Ok(())
+		})
+	}
+
+	/// The reserved balance of `currency_id` on `who` not held for a reason
+	/// through `HoldableCurrency`.
+	fn unheld_reserved_balance(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId) -> BalanceOf<T, I> {
+		<Self as Stp258CurrencyReservable<T::AccountId>>::reserved_balance(currency_id, who)
+			.saturating_sub(Self::total_balance_on_hold(currency_id, who))
+	}
+
+	/// Repatriate up to `value` reserved of `currency_id` on `slashed` to
+	/// `beneficiary` through the backing currency, returning the amount
+	/// not repatriated.
+	fn do_repatriate_reserved(
+		currency_id: CurrencyIdOf<T, I>,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: BalanceOf<T, I>,
+		status: BalanceStatus,
+	) -> result::Result<BalanceOf<T, I>, DispatchError> {
+		Self::track_checkpoints(currency_id, &[slashed, beneficiary], || {
+			Self::track_reserved(currency_id, &[slashed, beneficiary], || {
+				if currency_id == Self::native_currency_id() {
+					T::Stp258Native::repatriate_reserved(slashed, beneficiary, value, status)
+				} else {
+					T::Stp258Currency::repatriate_reserved(currency_id, slashed, beneficiary, value, status)
+				}
+			})
+		})
+	}
+
+	/// Unreserve up to `value` of `currency_id` on `who` from the backing
+	/// currency, returning the amount left reserved.
+	fn do_unreserve(currency_id: CurrencyIdOf<T, I>, who: &T::AccountId, value: BalanceOf<T, I>) -> BalanceOf<T, I> {
+		Self::track_reserved(currency_id, &[who], || {
+			if currency_id == Self::native_currency_id() {
+				T::Stp258Native::unreserve(who, value)
+			} else {
+				T::Stp258Currency::unreserve(currency_id, who, value)
+			}
+		})
+	}
+
+	/// Deposit `amount` of `currency_id` into `who`, failing with
+	/// `DeadAccount` if `who` holds none of `currency_id` yet.
+	pub fn deposit_into_existing(
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		amount: BalanceOf<T, I>,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		ensure!(
+			!<Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, who).is_zero(),
+			Error::<T, I>::DeadAccount
+		);
+		<Self as Stp258Currency<T::AccountId>>::deposit(currency_id, who, amount)
+	}
+
+	/// Check that the locks recorded by this pallet are enforced by the
+	/// backing currencies, and that `TotalLocked` and lock expiries agree
+	/// with `Locks`.
+	///
+	/// Catches drift introduced by other pallets removing or shrinking
+	/// locks directly on the backing currencies.
+	#[cfg(feature = "try-runtime")]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		let mut total_locked: Vec<(CurrencyIdOf<T, I>, BalanceOf<T, I>)> = Vec::new();
+		for (who, currency_id, locks) in Locks::<T, I>::iter() {
+			let max_lock = locks.iter().map(|lock| lock.amount).max().unwrap_or_else(Zero::zero);
+			match total_locked.iter_mut().find(|(id, _)| *id == currency_id) {
+				Some((_, total)) => *total = total.saturating_add(max_lock),
+				None => total_locked.push((currency_id, max_lock)),
+			}
+
+			// withdrawing into the lock must be refused by the backing currency
+			let free = <Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, &who);
+			if !free.is_zero() && !max_lock.is_zero() {
+				let into_lock = free.saturating_sub(max_lock).saturating_add(1u32.into()).min(free);
+				if <Self as Stp258Currency<T::AccountId>>::ensure_can_withdraw(currency_id, &who, into_lock).is_ok() {
+					return Err("lock recorded by stp258 is not enforced by the backing currency");
+				}
+			}
+		}
+
+		for (currency_id, total) in TotalLocked::<T, I>::iter() {
+			let expected = total_locked
+				.iter()
+				.find(|(id, _)| *id == currency_id)
+				.map(|(_, total)| *total)
+				.unwrap_or_else(Zero::zero);
+			if total != expected {
+				return Err("TotalLocked does not match the recorded locks");
+			}
+		}
+		if total_locked
+			.iter()
+			.any(|(currency_id, total)| !total.is_zero() && Self::total_locked(currency_id) != *total)
+		{
+			return Err("TotalLocked is missing recorded locks");
+		}
+
+		for (who, (currency_id, lock_id), until) in LockExpiry::<T, I>::iter() {
+			if !Self::locks(&who, currency_id).iter().any(|lock| lock.id == lock_id) {
+				return Err("lock expiry recorded for a removed lock");
+			}
+			if !Self::lock_expiry_queue(until)
+				.iter()
+				.any(|(queued, id, queued_lock)| *queued == who && *id == currency_id && *queued_lock == lock_id)
+			{
+				return Err("lock expiry is not queued");
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Send `slashed` funds, already removed from `who`, to the slash
+	/// destination of `currency_id`.
+	///
+	/// The backing currency burns slashed funds, so redirected funds are
+	/// issued again to their beneficiary.
+	fn settle_slash(
+		currency_id: CurrencyIdOf<T, I>,
+		who: &T::AccountId,
+		slashed: BalanceOf<T, I>,
+		order: Option<SlashOrder>,
+	) {
+		if slashed.is_zero() {
+			return;
+		}
+		let destination = Self::slash_destination(currency_id);
+		let credit = match &destination {
+			SlashDestination::Burn => None,
+			SlashDestination::Treasury => Some((T::TreasuryAccount::get(), slashed)),
+			SlashDestination::Account(account) => Some((account.clone(), slashed)),
+			SlashDestination::Split(ratio) => Some((T::TreasuryAccount::get(), *ratio * slashed)),
+		};
+		if let Some((beneficiary, amount)) = credit {
+			// through this pallet's `deposit`, so the credit is observed
+			if let Err(e) = <Self as Stp258Currency<T::AccountId>>::deposit(currency_id, &beneficiary, amount) {
+				log::warn!(
+					target: LOG_TARGET,
+					"slashed {:?} of {:?} under {:?} burned, crediting {:?} failed: {:?}",
+					amount,
+					who,
+					currency_id,
+					beneficiary,
+					e,
+				);
+			}
+		}
+		Self::deposit_event(Event::Slashed(
+			currency_id,
+			who.clone(),
+			slashed,
+			destination,
+			order,
+			Self::bump_operation_nonce(who),
+		));
+		T::OnSlash::on_slash(currency_id, who, slashed);
+	}
+}
+
+impl<T: Config<I>, I: 'static> InspectAccounts<T::AccountId> for Pallet<T, I> {
+	type CurrencyId = CurrencyIdOf<T, I>;
+	type Balance = BalanceOf<T, I>;
+	type PendingUpdate = PendingUpdateOf<T, I>;
+
+	fn usable_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		Pallet::<T, I>::usable_balance(currency_id, who)
+	}
+
+	fn all_pending_updates() -> Vec<(UpdateId, Self::PendingUpdate)> {
+		PendingUpdates::<T, I>::iter().collect()
+	}
+
+	fn pending_updates_of(who: &T::AccountId) -> Vec<(UpdateId, Self::PendingUpdate)> {
+		PendingUpdates::<T, I>::iter()
+			.filter(|(_, update)| update.who == *who)
+			.collect()
+	}
+
+	fn slash_destinations() -> Vec<(Self::CurrencyId, SlashDestination<T::AccountId>)> {
+		SlashDestinations::<T, I>::iter().collect()
+	}
+}
+
+impl<T: Config<I>, I: 'static> HoldableCurrency<T::AccountId> for Pallet<T, I> {
+	type CurrencyId = CurrencyIdOf<T, I>;
+	type Balance = BalanceOf<T, I>;
+	type Reason = T::HoldReason;
+
+	fn balance_on_hold(reason: Self::Reason, currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		Self::holds(who, currency_id)
+			.into_iter()
+			.find(|(held_for, _)| *held_for == reason)
+			.map_or_else(Zero::zero, |(_, held)| held)
+	}
+
+	fn total_balance_on_hold(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		Self::holds(who, currency_id)
+			.into_iter()
+			.fold(Zero::zero(), |total: Self::Balance, (_, held)| total.saturating_add(held))
+	}
+
+	fn hold(reason: Self::Reason, currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		<Self as Stp258CurrencyReservable<T::AccountId>>::reserve(currency_id, who, amount)?;
+		Holds::<T, I>::mutate(who, currency_id, |holds| {
+			match holds.iter_mut().find(|(held_for, _)| *held_for == reason) {
+				Some((_, held)) => *held = held.saturating_add(amount),
+				None => holds.push((reason, amount)),
+			}
+		});
+		Self::deposit_event(Event::Held(currency_id, who.clone(), reason, amount));
+		Ok(())
+	}
+
+	fn release(
+		reason: Self::Reason,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> Self::Balance {
+		let amount = amount.min(Self::balance_on_hold(reason, currency_id, who));
+		if amount.is_zero() {
+			return Zero::zero();
+		}
+		// a shortfall was slashed from the reserve, so it leaves the hold too
+		let released = amount.saturating_sub(Self::do_unreserve(currency_id, who, amount));
+		Holds::<T, I>::mutate_exists(who, currency_id, |maybe_holds| {
+			if let Some(holds) = maybe_holds {
+				for (held_for, held) in holds.iter_mut() {
+					if *held_for == reason {
+						*held = held.saturating_sub(amount);
+					}
+				}
+				holds.retain(|(_, held)| !held.is_zero());
+				if holds.is_empty() {
+					*maybe_holds = None;
+				}
+			}
+		});
+		Self::deposit_event(Event::Released(currency_id, who.clone(), reason, released));
+		released
+	}
+}
+
+impl<T: Config<I>, I: 'static> TransferVolume<T::AccountId> for Pallet<T, I> {
+	type CurrencyId = CurrencyIdOf<T, I>;
+	type Balance = BalanceOf<T, I>;
+
+	fn current_era() -> u32 {
+		Self::era_of(<frame_system::Module<T>>::block_number())
+	}
+
+	fn transfer_volume(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		match Self::account_activity(who, currency_id).transfer_volume {
+			Some((era, volume)) if era == Self::current_era() => volume,
+			_ => Zero::zero(),
 		}
 	}
 }
 
-impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
-	type CurrencyId = CurrencyIdOf<T>;
-	type Balance = BalanceOf<T>;
+impl<T: Config<I>, I: 'static> Stp258Currency<T::AccountId> for Pallet<T, I> {
+	type CurrencyId = CurrencyIdOf<T, I>;
+	type Balance = BalanceOf<T, I>;
 
 	fn base_unit(currency_id: Self::CurrencyId) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
+		if currency_id == Self::native_currency_id() {
 			T::Stp258Native::minimum_balance()
 		} else {
 			T::Stp258Currency::base_unit(currency_id)
@@ -168,7 +3612,7 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 	}
 
 	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
+		if currency_id == Self::native_currency_id() {
 			T::Stp258Native::minimum_balance()
 		} else {
 			T::Stp258Currency::minimum_balance(currency_id)
@@ -176,7 +3620,7 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 	}
 
 	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
+		if currency_id == Self::native_currency_id() {
 			T::Stp258Native::total_issuance()
 		} else {
 			T::Stp258Currency::total_issuance(currency_id)
@@ -184,7 +3628,7 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 	}
 
 	fn total_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
+		if currency_id == Self::native_currency_id() {
 			T::Stp258Native::total_balance(who)
 		} else {
 			T::Stp258Currency::total_balance(currency_id, who)
@@ -192,7 +3636,7 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 	}
 
 	fn free_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
+		if currency_id == Self::native_currency_id() {
 			T::Stp258Native::free_balance(who)
 		} else {
 			T::Stp258Currency::free_balance(currency_id, who)
@@ -200,7 +3644,7 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 	}
 
 	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		if currency_id == T::GetStp258NativeId::get() {
+		if currency_id == Self::native_currency_id() {
 			T::Stp258Native::ensure_can_withdraw(who, amount)
 		} else {
 			T::Stp258Currency::ensure_can_withdraw(currency_id, who, amount)
@@ -213,28 +3657,32 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 		to: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if amount.is_zero() || from == to {
-			return Ok(());
-		}
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::transfer(from, to, amount)?;
-		} else {
-			T::Stp258Currency::transfer(currency_id, from, to, amount)?;
-		}
-		Self::deposit_event(Event::Transferred(currency_id, from.clone(), to.clone(), amount));
-		Ok(())
+		Self::transfer_returning_actual(currency_id, from, to, amount).map(|_| ())
 	}
 
 	fn deposit(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::deposit(who, amount)?;
-		} else {
-			T::Stp258Currency::deposit(currency_id, who, amount)?;
-		}
-		Self::deposit_event(Event::Deposited(currency_id, who.clone(), amount));
+		with_transaction_result(|| {
+			Self::charge_demurrage(currency_id, who);
+			Self::trace_route(currency_id);
+			Self::track_checkpoints(currency_id, &[who], || {
+				if currency_id == Self::native_currency_id() {
+					T::Stp258Native::deposit(who, amount)
+				} else {
+					T::Stp258Currency::deposit(currency_id, who, amount)
+				}
+			})
+		})?;
+		Self::deposit_event(Event::Deposited(
+			currency_id,
+			who.clone(),
+			amount,
+			Self::bump_operation_nonce(who),
+		));
+		T::OnDeposit::on_deposit(currency_id, who, amount);
+		Self::auto_sweep(currency_id, who, amount);
 		Ok(())
 	}
 
@@ -242,17 +3690,34 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::withdraw(who, amount)?;
-		} else {
-			T::Stp258Currency::withdraw(currency_id, who, amount)?;
+		let withdrawn = with_transaction_result(|| {
+			Self::charge_demurrage(currency_id, who);
+			Self::trace_route(currency_id);
+			Self::track_checkpoints(currency_id, &[who], || {
+				if currency_id == Self::native_currency_id() {
+					T::Stp258Native::withdraw(who, amount)
+				} else {
+					T::Stp258Currency::withdraw(currency_id, who, amount)
+				}
+			})
+		});
+		#[cfg(feature = "metrics")]
+		if withdrawn.is_err() {
+			T::Metrics::on_failed_withdrawal(currency_id);
 		}
-		Self::deposit_event(Event::Withdrawn(currency_id, who.clone(), amount));
+		withdrawn?;
+		Self::deposit_event(Event::Withdrawn(
+			currency_id,
+			who.clone(),
+			amount,
+			Self::bump_operation_nonce(who),
+		));
+		T::OnWithdraw::on_withdraw(currency_id, who, amount);
 		Ok(())
 	}
 
 	fn can_slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> bool {
-		if currency_id == T::GetStp258NativeId::get() {
+		if currency_id == Self::native_currency_id() {
 			T::Stp258Native::can_slash(who, amount)
 		} else {
 			T::Stp258Currency::can_slash(currency_id, who, amount)
@@ -260,29 +3725,84 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 	}
 
 	fn slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::slash(who, amount)
-		} else {
-			T::Stp258Currency::slash(currency_id, who, amount)
-		}
+		let native = currency_id == Self::native_currency_id();
+		// balance held for a reason is not slashed, and the backing
+		// currencies slash free balance first, so neither closure reaches it
+		let slash = |amount: BalanceOf<T, I>| {
+			let slashable = amount.min(
+				Self::free_balance(currency_id, who).saturating_add(Self::unheld_reserved_balance(currency_id, who)),
+			);
+			let gap = if native {
+				T::Stp258Native::slash(who, slashable)
+			} else {
+				T::Stp258Currency::slash(currency_id, who, slashable)
+			};
+			amount.saturating_sub(slashable).saturating_add(gap)
+		};
+		let slash_reserved = |amount: BalanceOf<T, I>| {
+			let slashable = amount.min(Self::unheld_reserved_balance(currency_id, who));
+			let gap = if native {
+				T::Stp258Native::slash_reserved(who, slashable)
+			} else {
+				T::Stp258Currency::slash_reserved(currency_id, who, slashable)
+			};
+			amount.saturating_sub(slashable).saturating_add(gap)
+		};
+		let order = Self::slash_order(currency_id);
+		let slash_in_order = || match order {
+			SlashOrder::FreeFirst => slash(amount),
+			SlashOrder::ReservedFirst => slash(slash_reserved(amount)),
+			SlashOrder::Proportional => {
+				let reserved = Self::unheld_reserved_balance(currency_id, who);
+				let total = Self::free_balance(currency_id, who).saturating_add(reserved);
+				if total.is_zero() {
+					return amount;
+				}
+				let from_reserved: BalanceOf<T, I> =
+					FixedU128::saturating_from_rational(reserved.saturated_into::<u128>(), total.saturated_into::<u128>())
+						.saturating_mul_int(amount.saturated_into::<u128>())
+						.saturated_into();
+				let from_reserved = from_reserved.min(amount);
+				slash(amount.saturating_sub(from_reserved).saturating_add(slash_reserved(from_reserved)))
+			}
+		};
+		let gap = Self::track_checkpoints(currency_id, &[who], || {
+			Self::track_reserved(currency_id, &[who], slash_in_order)
+		});
+		Self::settle_slash(currency_id, who, amount.saturating_sub(gap), Some(order));
+		gap
 	}
 }
 
-impl<T: Config> Stp258CurrencyExtended<T::AccountId> for Pallet<T> {
-	type Amount = AmountOf<T>;
+impl<T: Config<I>, I: 'static> Stp258CurrencyExtended<T::AccountId> for Pallet<T, I> {
+	type Amount = AmountOf<T, I>;
 
 	fn update_balance(currency_id: Self::CurrencyId, who: &T::AccountId, by_amount: Self::Amount) -> DispatchResult {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::update_balance(who, by_amount)?;
-		} else {
-			T::Stp258Currency::update_balance(currency_id, who, by_amount)?;
+		let before = <Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, who);
+		Self::track_checkpoints(currency_id, &[who], || {
+			if currency_id == Self::native_currency_id() {
+				T::Stp258Native::update_balance(who, by_amount)
+			} else {
+				T::Stp258Currency::update_balance(currency_id, who, by_amount)
+			}
+		})?;
+		Self::deposit_event(Event::BalanceUpdated(
+			currency_id,
+			who.clone(),
+			by_amount,
+			Self::bump_operation_nonce(who),
+		));
+		let after = <Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, who);
+		if after > before {
+			T::OnDeposit::on_deposit(currency_id, who, after - before);
+		} else if before > after {
+			T::OnWithdraw::on_withdraw(currency_id, who, before - after);
 		}
-		Self::deposit_event(Event::BalanceUpdated(currency_id, who.clone(), by_amount));
 		Ok(())
 	}
 }
 
-impl<T: Config> Stp258CurrencyLockable<T::AccountId> for Pallet<T> {
+impl<T: Config<I>, I: 'static> Stp258CurrencyLockable<T::AccountId> for Pallet<T, I> {
 	type Moment = T::BlockNumber;
 
 	fn set_lock(
@@ -291,11 +3811,16 @@ impl<T: Config> Stp258CurrencyLockable<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::set_lock(lock_id, who, amount)
+		Self::ensure_lock_id_registered(&lock_id)?;
+		Self::ensure_can_add_lock(lock_id, currency_id, who)?;
+		if currency_id == Self::native_currency_id() {
+			T::Stp258Native::set_lock(lock_id, who, amount)?;
 		} else {
-			T::Stp258Currency::set_lock(lock_id, currency_id, who, amount)
+			T::Stp258Currency::set_lock(lock_id, currency_id, who, amount)?;
 		}
+		LockExpiry::<T, I>::remove(who, (currency_id, lock_id));
+		Self::track_lock(lock_id, currency_id, who, Some(amount));
+		Ok(())
 	}
 
 	fn extend_lock(
@@ -304,25 +3829,36 @@ impl<T: Config> Stp258CurrencyLockable<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::extend_lock(lock_id, who, amount)
+		Self::ensure_lock_id_registered(&lock_id)?;
+		Self::ensure_can_add_lock(lock_id, currency_id, who)?;
+		if currency_id == Self::native_currency_id() {
+			T::Stp258Native::extend_lock(lock_id, who, amount)?;
 		} else {
-			T::Stp258Currency::extend_lock(lock_id, currency_id, who, amount)
+			T::Stp258Currency::extend_lock(lock_id, currency_id, who, amount)?;
 		}
+		let extended = Self::locks(who, currency_id)
+			.iter()
+			.find(|lock| lock.id == lock_id)
+			.map_or(amount, |lock| lock.amount.max(amount));
+		Self::track_lock(lock_id, currency_id, who, Some(extended));
+		Ok(())
 	}
 
 	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::remove_lock(lock_id, who)
+		if currency_id == Self::native_currency_id() {
+			T::Stp258Native::remove_lock(lock_id, who)?;
 		} else {
-			T::Stp258Currency::remove_lock(lock_id, currency_id, who)
+			T::Stp258Currency::remove_lock(lock_id, currency_id, who)?;
 		}
+		LockExpiry::<T, I>::remove(who, (currency_id, lock_id));
+		Self::track_lock(lock_id, currency_id, who, None);
+		Ok(())
 	}
 }
 
-impl<T: Config> Stp258CurrencyReservable<T::AccountId> for Pallet<T> {
+impl<T: Config<I>, I: 'static> Stp258CurrencyReservable<T::AccountId> for Pallet<T, I> {
 	fn can_reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> bool {
-		if currency_id == T::GetStp258NativeId::get() {
+		if currency_id == Self::native_currency_id() {
 			T::Stp258Native::can_reserve(who, value)
 		} else {
 			T::Stp258Currency::can_reserve(currency_id, who, value)
@@ -330,15 +3866,23 @@ impl<T: Config> Stp258CurrencyReservable<T::AccountId> for Pallet<T> {
 	}
 
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::slash_reserved(who, value)
-		} else {
-			T::Stp258Currency::slash_reserved(currency_id, who, value)
-		}
+		// balance held for a reason is not slashed from the reserve
+		let unheld = Self::unheld_reserved_balance(currency_id, who).min(value);
+		let gap = Self::track_checkpoints(currency_id, &[who], || {
+			Self::track_reserved(currency_id, &[who], || {
+				if currency_id == Self::native_currency_id() {
+					T::Stp258Native::slash_reserved(who, unheld)
+				} else {
+					T::Stp258Currency::slash_reserved(currency_id, who, unheld)
+				}
+			})
+		});
+		Self::settle_slash(currency_id, who, unheld.saturating_sub(gap), None);
+		value.saturating_sub(unheld).saturating_add(gap)
 	}
 
 	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
+		if currency_id == Self::native_currency_id() {
 			T::Stp258Native::reserved_balance(who)
 		} else {
 			T::Stp258Currency::reserved_balance(currency_id, who)
@@ -346,19 +3890,21 @@ impl<T: Config> Stp258CurrencyReservable<T::AccountId> for Pallet<T> {
 	}
 
 	fn reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::reserve(who, value)
-		} else {
-			T::Stp258Currency::reserve(currency_id, who, value)
-		}
+		Self::track_reserved(currency_id, &[who], || {
+			if currency_id == Self::native_currency_id() {
+				T::Stp258Native::reserve(who, value)
+			} else {
+				T::Stp258Currency::reserve(currency_id, who, value)
+			}
+		})
 	}
 
 	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::unreserve(who, value)
-		} else {
-			T::Stp258Currency::unreserve(currency_id, who, value)
-		}
+		// balance held for a reason is only unreserved through `release`
+		let unheld = Self::unheld_reserved_balance(currency_id, who).min(value);
+		value
+			.saturating_sub(unheld)
+			.saturating_add(Self::do_unreserve(currency_id, who, unheld))
 	}
 
 	fn repatriate_reserved(
@@ -368,119 +3914,216 @@ impl<T: Config> Stp258CurrencyReservable<T::AccountId> for Pallet<T> {
 		value: Self::Balance,
 		status: BalanceStatus,
 	) -> result::Result<Self::Balance, DispatchError> {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::repatriate_reserved(slashed, beneficiary, value, status)
-		} else {
-			T::Stp258Currency::repatriate_reserved(currency_id, slashed, beneficiary, value, status)
+		// balance held for a reason is not repatriated
+		let moved = value.min(Self::unheld_reserved_balance(currency_id, slashed));
+		let minimum = <Self as Stp258Currency<T::AccountId>>::minimum_balance(currency_id);
+		let total = <Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, beneficiary);
+		if slashed == beneficiary || moved.is_zero() || total.saturating_add(moved) >= minimum {
+			let remaining = Self::do_repatriate_reserved(currency_id, slashed, beneficiary, moved, status)?;
+			return Ok(value.saturating_sub(moved).saturating_add(remaining));
+		}
+
+		let fallback = T::ReleaseFallback::get();
+		let treasury = T::TreasuryAccount::get();
+		let remaining = match fallback {
+			ReleaseFallback::Treasury => Self::do_repatriate_reserved(currency_id, slashed, &treasury, moved, status)?,
+			ReleaseFallback::KeepReserved => moved,
+			// the beneficiary is credited in one transfer from the treasury,
+			// so it is never left below the existential deposit
+			ReleaseFallback::TopUp => with_transaction_result(|| {
+				let remaining =
+					Self::do_repatriate_reserved(currency_id, slashed, &treasury, moved, BalanceStatus::Free)?;
+				let credited = moved.saturating_sub(remaining);
+				if credited.is_zero() {
+					return Ok(remaining);
+				}
+				<Self as Stp258Currency<T::AccountId>>::transfer(
+					currency_id,
+					&treasury,
+					beneficiary,
+					minimum.saturating_sub(total),
+				)?;
+				if status == BalanceStatus::Reserved {
+					<Self as Stp258CurrencyReservable<T::AccountId>>::reserve(currency_id, beneficiary, credited)?;
+				}
+				Ok(remaining)
+			})?,
+		};
+		Self::deposit_event(Event::ReleaseFallbackApplied(
+			currency_id,
+			beneficiary.clone(),
+			moved,
+			fallback,
+		));
+		Ok(value.saturating_sub(moved).saturating_add(remaining))
+	}
+}
+
+/// Moves the dust of the non-native currencies to the currency's dust
+/// account, for redistribution at the end of the era.
+///
+/// Set as the backing currencies' dust handler. Native dust is handled by
+/// the native currency's own dust removal.
+pub struct AggregateDust<T, I = ()>(marker::PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> OnDust<T::AccountId, CurrencyIdOf<T, I>, BalanceOf<T, I>> for AggregateDust<T, I> {
+	fn on_dust(who: &T::AccountId, currency_id: CurrencyIdOf<T, I>, amount: BalanceOf<T, I>) {
+		let dust_account = Pallet::<T, I>::dust_account(currency_id);
+		// the backing currency directly, dust is not a user transfer
+		match T::Stp258Currency::transfer(currency_id, who, &dust_account, amount) {
+			Ok(()) => {
+				AccumulatedDust::<T, I>::mutate(currency_id, |total| *total = total.saturating_add(amount));
+				Pallet::<T, I>::deposit_event(Event::DustAggregated(currency_id, who.clone(), amount));
+			}
+			Err(e) => log::warn!(
+				target: LOG_TARGET,
+				"aggregating {:?} dust of {:?} under {:?} failed: {:?}",
+				amount,
+				who,
+				currency_id,
+				e,
+			),
 		}
 	}
 }
 
-pub struct Currency<T, GetCurrencyId>(marker::PhantomData<T>, marker::PhantomData<GetCurrencyId>);
+pub struct Currency<T, GetCurrencyId, I = ()>(
+	marker::PhantomData<T>,
+	marker::PhantomData<GetCurrencyId>,
+	marker::PhantomData<I>,
+);
 
-impl<T, GetCurrencyId> Stp258Asset<T::AccountId> for Currency<T, GetCurrencyId>
+impl<T, GetCurrencyId, I> Stp258Asset<T::AccountId> for Currency<T, GetCurrencyId, I>
 where
-	T: Config,
-	GetCurrencyId: Get<CurrencyIdOf<T>>,
+	T: Config<I>,
+	I: 'static,
+	GetCurrencyId: Get<CurrencyIdOf<T, I>>,
 {
-	type Balance = BalanceOf<T>;
+	type Balance = BalanceOf<T, I>;
 
 	fn minimum_balance() -> Self::Balance {
-		<Pallet<T>>::minimum_balance(GetCurrencyId::get())
+		<Pallet<T, I>>::minimum_balance(GetCurrencyId::get())
 	}
 
 	fn total_issuance() -> Self::Balance {
-		<Pallet<T>>::total_issuance(GetCurrencyId::get())
+		<Pallet<T, I>>::total_issuance(GetCurrencyId::get())
 	}
 
 	fn total_balance(who: &T::AccountId) -> Self::Balance {
-		<Pallet<T>>::total_balance(GetCurrencyId::get(), who)
+		<Pallet<T, I>>::total_balance(GetCurrencyId::get(), who)
 	}
 
 	fn free_balance(who: &T::AccountId) -> Self::Balance {
-		<Pallet<T>>::free_balance(GetCurrencyId::get(), who)
+		<Pallet<T, I>>::free_balance(GetCurrencyId::get(), who)
 	}
 
 	fn ensure_can_withdraw(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		<Pallet<T>>::ensure_can_withdraw(GetCurrencyId::get(), who, amount)
+		<Pallet<T, I>>::ensure_can_withdraw(GetCurrencyId::get(), who, amount)
 	}
 
 	fn transfer(from: &T::AccountId, to: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		<Pallet<T> as Stp258Currency<T::AccountId>>::transfer(GetCurrencyId::get(), from, to, amount)
+		<Pallet<T, I> as Stp258Currency<T::AccountId>>::transfer(GetCurrencyId::get(), from, to, amount)
 	}
 
 	fn deposit(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		<Pallet<T>>::deposit(GetCurrencyId::get(), who, amount)
+		<Pallet<T, I>>::deposit(GetCurrencyId::get(), who, amount)
 	}
 
 	fn withdraw(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		<Pallet<T>>::withdraw(GetCurrencyId::get(), who, amount)
+		<Pallet<T, I>>::withdraw(GetCurrencyId::get(), who, amount)
 	}
 
 	fn can_slash(who: &T::AccountId, amount: Self::Balance) -> bool {
-		<Pallet<T>>::can_slash(GetCurrencyId::get(), who, amount)
+		<Pallet<T, I>>::can_slash(GetCurrencyId::get(), who, amount)
 	}
 
 	fn slash(who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
-		<Pallet<T>>::slash(GetCurrencyId::get(), who, amount)
+		<Pallet<T, I>>::slash(GetCurrencyId::get(), who, amount)
+	}
+}
+
+impl<T, GetCurrencyId, I> Currency<T, GetCurrencyId, I>
+where
+	T: Config<I>,
+	I: 'static,
+	GetCurrencyId: Get<CurrencyIdOf<T, I>>,
+{
+	/// The free balance of `who` minus the largest lock set through this
+	/// pallet.
+	pub fn usable_balance(who: &T::AccountId) -> BalanceOf<T, I> {
+		<Pallet<T, I>>::usable_balance(GetCurrencyId::get(), who)
+	}
+
+	/// Check whether `amount` can be withdrawn from `who`, and why not.
+	pub fn can_withdraw(who: &T::AccountId, amount: BalanceOf<T, I>) -> WithdrawConsequence<BalanceOf<T, I>> {
+		<Pallet<T, I>>::can_withdraw(GetCurrencyId::get(), who, amount)
+	}
+
+	/// Deposit `amount` into `who`, failing if `who` holds none of this
+	/// currency yet.
+	pub fn deposit_into_existing(who: &T::AccountId, amount: BalanceOf<T, I>) -> DispatchResult {
+		<Pallet<T, I>>::deposit_into_existing(GetCurrencyId::get(), who, amount)
 	}
 }
 
-impl<T, GetCurrencyId> Stp258AssetExtended<T::AccountId> for Currency<T, GetCurrencyId>
+impl<T, GetCurrencyId, I> Stp258AssetExtended<T::AccountId> for Currency<T, GetCurrencyId, I>
 where
-	T: Config,
-	GetCurrencyId: Get<CurrencyIdOf<T>>,
+	T: Config<I>,
+	I: 'static,
+	GetCurrencyId: Get<CurrencyIdOf<T, I>>,
 {
-	type Amount = AmountOf<T>;
+	type Amount = AmountOf<T, I>;
 
 	fn update_balance(who: &T::AccountId, by_amount: Self::Amount) -> DispatchResult {
-		<Pallet<T> as Stp258CurrencyExtended<T::AccountId>>::update_balance(GetCurrencyId::get(), who, by_amount)
+		<Pallet<T, I> as Stp258CurrencyExtended<T::AccountId>>::update_balance(GetCurrencyId::get(), who, by_amount)
 	}
 }
 
-impl<T, GetCurrencyId> Stp258AssetLockable<T::AccountId> for Currency<T, GetCurrencyId>
+impl<T, GetCurrencyId, I> Stp258AssetLockable<T::AccountId> for Currency<T, GetCurrencyId, I>
 where
-	T: Config,
-	GetCurrencyId: Get<CurrencyIdOf<T>>,
+	T: Config<I>,
+	I: 'static,
+	GetCurrencyId: Get<CurrencyIdOf<T, I>>,
 {
 	type Moment = T::BlockNumber;
 
 	fn set_lock(lock_id: LockIdentifier, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		<Pallet<T> as Stp258CurrencyLockable<T::AccountId>>::set_lock(lock_id, GetCurrencyId::get(), who, amount)
+		<Pallet<T, I> as Stp258CurrencyLockable<T::AccountId>>::set_lock(lock_id, GetCurrencyId::get(), who, amount)
 	}
 
 	fn extend_lock(lock_id: LockIdentifier, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		<Pallet<T> as Stp258CurrencyLockable<T::AccountId>>::extend_lock(lock_id, GetCurrencyId::get(), who, amount)
+		<Pallet<T, I> as Stp258CurrencyLockable<T::AccountId>>::extend_lock(lock_id, GetCurrencyId::get(), who, amount)
 	}
 
 	fn remove_lock(lock_id: LockIdentifier, who: &T::AccountId) -> DispatchResult {
-		<Pallet<T> as Stp258CurrencyLockable<T::AccountId>>::remove_lock(lock_id, GetCurrencyId::get(), who)
+		<Pallet<T, I> as Stp258CurrencyLockable<T::AccountId>>::remove_lock(lock_id, GetCurrencyId::get(), who)
 	}
 }
 
-impl<T, GetCurrencyId> Stp258AssetReservable<T::AccountId> for Currency<T, GetCurrencyId>
+impl<T, GetCurrencyId, I> Stp258AssetReservable<T::AccountId> for Currency<T, GetCurrencyId, I>
 where
-	T: Config,
-	GetCurrencyId: Get<CurrencyIdOf<T>>,
+	T: Config<I>,
+	I: 'static,
+	GetCurrencyId: Get<CurrencyIdOf<T, I>>,
 {
 	fn can_reserve(who: &T::AccountId, value: Self::Balance) -> bool {
-		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::can_reserve(GetCurrencyId::get(), who, value)
+		<Pallet<T, I> as Stp258CurrencyReservable<T::AccountId>>::can_reserve(GetCurrencyId::get(), who, value)
 	}
 
 	fn slash_reserved(who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::slash_reserved(GetCurrencyId::get(), who, value)
+		<Pallet<T, I> as Stp258CurrencyReservable<T::AccountId>>::slash_reserved(GetCurrencyId::get(), who, value)
 	}
 
 	fn reserved_balance(who: &T::AccountId) -> Self::Balance {
-		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::reserved_balance(GetCurrencyId::get(), who)
+		<Pallet<T, I> as Stp258CurrencyReservable<T::AccountId>>::reserved_balance(GetCurrencyId::get(), who)
 	}
 
 	fn reserve(who: &T::AccountId, value: Self::Balance) -> DispatchResult {
-		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::reserve(GetCurrencyId::get(), who, value)
+		<Pallet<T, I> as Stp258CurrencyReservable<T::AccountId>>::reserve(GetCurrencyId::get(), who, value)
 	}
 
 	fn unreserve(who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::unreserve(GetCurrencyId::get(), who, value)
+		<Pallet<T, I> as Stp258CurrencyReservable<T::AccountId>>::unreserve(GetCurrencyId::get(), who, value)
 	}
 
 	fn repatriate_reserved(
@@ -489,7 +4132,7 @@ where
 		value: Self::Balance,
 		status: BalanceStatus,
 	) -> result::Result<Self::Balance, DispatchError> {
-		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::repatriate_reserved(
+		<Pallet<T, I> as Stp258CurrencyReservable<T::AccountId>>::repatriate_reserved(
 			GetCurrencyId::get(),
 			slashed,
 			beneficiary,
@@ -499,19 +4142,44 @@ where
 	}
 }
 
-pub type Stp258NativeOf<T> = Currency<T, <T as Config>::GetStp258NativeId>;
+/// The native currency id, honouring `NativeCurrencyIdOverride`.
+pub struct NativeCurrencyId<T, I = ()>(marker::PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> Get<CurrencyIdOf<T, I>> for NativeCurrencyId<T, I> {
+	fn get() -> CurrencyIdOf<T, I> {
+		<Pallet<T, I>>::native_currency_id()
+	}
+}
+
+pub type Stp258NativeOf<T, I = ()> = Currency<T, NativeCurrencyId<T, I>, I>;
 
 /// Adapt other currency traits implementation to `Stp258Asset`.
-pub struct Stp258AssetAdapter<T, Currency, Amount, Moment>(marker::PhantomData<(T, Currency, Amount, Moment)>);
+pub struct Stp258AssetAdapter<T, Currency, Amount, Moment, I = ()>(
+	marker::PhantomData<(T, Currency, Amount, Moment, I)>,
+);
 
 type PalletBalanceOf<A, Currency> = <Currency as SetheumCurrency<A>>::Balance;
 
+impl<T, Currency, Amount, Moment, I> Stp258AssetAdapter<T, Currency, Amount, Moment, I> {
+	/// Deposit `amount` into `who`, failing if `who` does not exist yet.
+	pub fn deposit_into_existing<AccountId>(
+		who: &AccountId,
+		amount: PalletBalanceOf<AccountId, Currency>,
+	) -> DispatchResult
+	where
+		Currency: SetheumCurrency<AccountId>,
+	{
+		Currency::deposit_into_existing(who, amount).map(|_| ())
+	}
+}
+
 // Adapt `frame_support::traits::Currency`
-impl<T, AccountId, Currency, Amount, Moment> Stp258Asset<AccountId>
-	for Stp258AssetAdapter<T, Currency, Amount, Moment>
+impl<T, AccountId, Currency, Amount, Moment, I> Stp258Asset<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, I>
 where
 	Currency: SetheumCurrency<AccountId>,
-	T: Config,
+	T: Config<I>,
+	I: 'static,
 {
 	type Balance = PalletBalanceOf<AccountId, Currency>;
 
@@ -534,7 +4202,7 @@ where
 	fn ensure_can_withdraw(who: &AccountId, amount: Self::Balance) -> DispatchResult {
 		let new_balance = Self::free_balance(who)
 			.checked_sub(&amount)
-			.ok_or(Error::<T>::BalanceTooLow)?;
+			.ok_or(Error::<T, I>::BalanceTooLow)?;
 
 		Currency::ensure_can_withdraw(who, amount, WithdrawReasons::all(), new_balance)
 	}
@@ -544,7 +4212,12 @@ where
 	}
 
 	fn deposit(who: &AccountId, amount: Self::Balance) -> DispatchResult {
-		let _ = Currency::deposit_creating(who, amount);
+		if amount.is_zero() {
+			return Ok(());
+		}
+		// the backing currency drops a deposit that cannot create `who`
+		let deposited = Currency::deposit_creating(who, amount);
+		ensure!(!deposited.peek().is_zero(), Error::<T, I>::DeadAccount);
 		Ok(())
 	}
 
@@ -563,8 +4236,8 @@ where
 }
 
 // Adapt `frame_support::traits::Currency`
-impl<T, AccountId, Currency, Amount, Moment> Stp258AssetExtended<AccountId>
-	for Stp258AssetAdapter<T, Currency, Amount, Moment>
+impl<T, AccountId, Currency, Amount, Moment, I> Stp258AssetExtended<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, I>
 where
 	Amount: Signed
 		+ TryInto<PalletBalanceOf<AccountId, Currency>>
@@ -576,15 +4249,14 @@ where
 		+ Debug
 		+ Default,
 	Currency: SetheumCurrency<AccountId>,
-	T: Config,
+	T: Config<I>,
+	I: 'static,
 {
 	type Amount = Amount;
 
 	fn update_balance(who: &AccountId, by_amount: Self::Amount) -> DispatchResult {
-		let by_balance = by_amount
-			.abs()
-			.try_into()
-			.map_err(|_| Error::<T>::AmountIntoBalanceFailed)?;
+		let by_balance =
+			conversions::amount_to_balance_checked(by_amount).ok_or(Error::<T, I>::AmountIntoBalanceFailed)?;
 		if by_amount.is_positive() {
 			Self::deposit(who, by_balance)
 		} else {
@@ -594,11 +4266,12 @@ where
 }
 
 // Adapt `frame_support::traits::LockableCurrency`
-impl<T, AccountId, Currency, Amount, Moment> Stp258AssetLockable<AccountId>
-	for Stp258AssetAdapter<T, Currency, Amount, Moment>
+impl<T, AccountId, Currency, Amount, Moment, I> Stp258AssetLockable<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, I>
 where
 	Currency: SetheumLockableCurrency<AccountId>,
-	T: Config,
+	T: Config<I>,
+	I: 'static,
 {
 	type Moment = Moment;
 
@@ -619,11 +4292,12 @@ where
 }
 
 // Adapt `frame_support::traits::ReservableCurrency`
-impl<T, AccountId, Currency, Amount, Moment> Stp258AssetReservable<AccountId>
-	for Stp258AssetAdapter<T, Currency, Amount, Moment>
+impl<T, AccountId, Currency, Amount, Moment, I> Stp258AssetReservable<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, I>
 where
 	Currency: SetheumReservableCurrency<AccountId>,
-	T: Config,
+	T: Config<I>,
+	I: 'static,
 {
 	fn can_reserve(who: &AccountId, value: Self::Balance) -> bool {
 		Currency::can_reserve(who, value)
@@ -656,7 +4330,7 @@ where
 	}
 }
 
-impl<T: Config> MergeAccount<T::AccountId> for Pallet<T> {
+impl<T: Config<I>, I: 'static> MergeAccount<T::AccountId> for Pallet<T, I> {
 	fn merge_account(source: &T::AccountId, dest: &T::AccountId) -> DispatchResult {
 		with_transaction_result(|| {
 			// transfer non-native free to dest
@@ -0,0 +1,31 @@
+//! Namespaced lock identifiers.
+//!
+//! A namespaced lock identifier is made of the `NAMESPACE_MARKER` byte, a
+//! three byte `LockPrefix` registered with the pallet, and a four byte id
+//! chosen by the owner of the prefix. Lock identifiers that do not start
+//! with the marker are raw identifiers and are not checked.
+
+use serp_traits::LockIdentifier;
+
+/// The first byte of every namespaced lock identifier.
+pub const NAMESPACE_MARKER: u8 = b'#';
+
+/// The namespace part of a namespaced lock identifier.
+pub type LockPrefix = [u8; 3];
+
+/// Build the namespaced lock identifier `id` under `prefix`.
+pub fn namespaced(prefix: LockPrefix, id: u32) -> LockIdentifier {
+	let mut lock_id = [0u8; 8];
+	lock_id[0] = NAMESPACE_MARKER;
+	lock_id[1..4].copy_from_slice(&prefix);
+	lock_id[4..].copy_from_slice(&id.to_le_bytes());
+	lock_id
+}
+
+/// The prefix of `lock_id`, `None` if it is a raw identifier.
+pub fn prefix_of(lock_id: &LockIdentifier) -> Option<LockPrefix> {
+	if lock_id[0] != NAMESPACE_MARKER {
+		return None;
+	}
+	Some([lock_id[1], lock_id[2], lock_id[3]])
+}
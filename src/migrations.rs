@@ -0,0 +1,444 @@
+//! Storage migrations for the Stp258Standard module.
+
+use super::*;
+use frame_support::traits::OnRuntimeUpgrade;
+
+/// Migrate storage from `Releases::V1` to `Releases::V2`.
+///
+/// The lock prefix registry starts empty, while `TotalLocked` is rebuilt
+/// from the locks already recorded in `Locks`.
+pub struct MigrateToV2<T, I = ()>(marker::PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV2<T, I> {
+	fn on_runtime_upgrade() -> Weight {
+		if StorageVersion::<T, I>::get() >= Releases::V2 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut accounts: Weight = 0;
+		TotalLocked::<T, I>::remove_all();
+		for (_, currency_id, locks) in Locks::<T, I>::iter() {
+			accounts += 1;
+			let max_lock = locks.iter().map(|lock| lock.amount).max().unwrap_or_else(Zero::zero);
+			TotalLocked::<T, I>::mutate(currency_id, |total| *total = total.saturating_add(max_lock));
+		}
+		StorageVersion::<T, I>::put(Releases::V2);
+
+		T::DbWeight::get().reads_writes(accounts.saturating_add(1), accounts.saturating_add(2))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		if StorageVersion::<T, I>::get() < Releases::V2 {
+			return Err("storage version was not updated to V2");
+		}
+		<Pallet<T, I>>::do_try_state()
+	}
+}
+
+/// Re-encode the balances and amounts stored by this pallet after the
+/// runtime widened `Balance` from `OldBalance` and `Amount` from
+/// `OldAmount`, e.g. from `u64`/`i64` to `u128`/`i128`.
+///
+/// Must run in the same runtime upgrade that widens the types. Runs once,
+/// from `Releases::V2` to `Releases::V3`, so `MigrateToV2` must run before
+/// it on chains still at `Releases::V1`.
+pub struct WidenBalance<T, OldBalance, OldAmount, I = ()>(marker::PhantomData<(T, OldBalance, OldAmount, I)>);
+
+impl<T, OldBalance, OldAmount, I> OnRuntimeUpgrade for WidenBalance<T, OldBalance, OldAmount, I>
+where
+	T: Config<I>,
+	I: 'static,
+	OldBalance: Decode + Into<BalanceOf<T, I>>,
+	OldAmount: Decode + Into<AmountOf<T, I>>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		widen_balance::migrate::<T, I, OldBalance, OldAmount>()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		widen_balance::pre_upgrade::<T, I, OldBalance>()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		widen_balance::post_upgrade::<T, I>()
+	}
+}
+
+/// The balance widening migration, usable on its own from a runtime's
+/// `OnRuntimeUpgrade`.
+pub mod widen_balance {
+	use super::*;
+	#[cfg(feature = "try-runtime")]
+	use frame_support::storage::{unhashed, StoragePrefixedMap};
+
+	#[cfg(feature = "try-runtime")]
+	const ENTRIES_KEY: &[u8] = b":stp258_standard:widen_balance:entries";
+	#[cfg(feature = "try-runtime")]
+	const SUM_KEY: &[u8] = b":stp258_standard:widen_balance:sum";
+
+	/// Translate every stored balance and amount and move to
+	/// `Releases::V3`, returning the weight used. Does nothing unless the
+	/// storage is at `Releases::V2`.
+	pub fn migrate<T, I, OldBalance, OldAmount>() -> Weight
+	where
+		T: Config<I>,
+		I: 'static,
+		OldBalance: Decode + Into<BalanceOf<T, I>>,
+		OldAmount: Decode + Into<AmountOf<T, I>>,
+	{
+		if StorageVersion::<T, I>::get() != Releases::V2 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut translated: Weight = 0;
+
+		TotalLocked::<T, I>::translate(|_, total: OldBalance| {
+			translated += 1;
+			Some(total.into())
+		});
+		TotalReserved::<T, I>::translate(|_, total: OldBalance| {
+			translated += 1;
+			Some(total.into())
+		});
+		Locks::<T, I>::translate(|_, _, locks: Vec<BalanceLock<OldBalance>>| {
+			translated += 1;
+			Some(
+				locks
+					.into_iter()
+					.map(|lock| BalanceLock {
+						id: lock.id,
+						amount: lock.amount.into(),
+					})
+					.collect(),
+			)
+		});
+		PendingUpdates::<T, I>::translate(
+			|_, update: PendingUpdate<T::AccountId, CurrencyIdOf<T, I>, OldAmount, T::BlockNumber>| {
+				translated += 1;
+				Some(PendingUpdate {
+					who: update.who,
+					currency_id: update.currency_id,
+					amount: update.amount.into(),
+					unlock_at: update.unlock_at,
+				})
+			},
+		);
+		SupplyHistory::<T, I>::translate(
+			|_, history: Vec<SupplyChange<OldAmount, T::BlockNumber, T::Hash>>| {
+				translated += 1;
+				Some(
+					history
+						.into_iter()
+						.map(|change| SupplyChange {
+							block: change.block,
+							amount: change.amount.into(),
+							source: change.source,
+						})
+						.collect(),
+				)
+			},
+		);
+		SerpParameters::<T, I>::translate(|_, parameter: SerpParameter<T::BlockNumber, OldBalance>| {
+			translated += 1;
+			Some(match parameter {
+				SerpParameter::AdjustmentFrequency(frequency) => SerpParameter::AdjustmentFrequency(frequency),
+				SerpParameter::SerpQuoteMultiple(multiple) => SerpParameter::SerpQuoteMultiple(multiple.into()),
+				SerpParameter::SerperRatio(ratio) => SerpParameter::SerperRatio(ratio),
+				SerpParameter::SettPayRatio(ratio) => SerpParameter::SettPayRatio(ratio),
+			})
+		});
+		let _ = NativeIssuanceSchedule::<T, I>::translate(|schedule: Option<IssuanceSchedule<OldBalance>>| {
+			schedule.map(|schedule| {
+				translated += 1;
+				match schedule {
+					IssuanceSchedule::Fixed(amount) => IssuanceSchedule::Fixed(amount.into()),
+					IssuanceSchedule::Decaying { initial, decay } => IssuanceSchedule::Decaying {
+						initial: initial.into(),
+						decay,
+					},
+				}
+			})
+		});
+		let _ = NextEraIssuance::<T, I>::translate(|issuance: Option<OldBalance>| {
+			issuance.map(|issuance| {
+				translated += 1;
+				issuance.into()
+			})
+		});
+		Savings::<T, I>::translate(|_, _, savings: SavingsBalance<OldBalance>| {
+			translated += 1;
+			Some(SavingsBalance {
+				shares: savings.shares.into(),
+				principal: savings.principal.into(),
+			})
+		});
+		AccountActivities::<T, I>::translate(|_, _, activity: AccountActivity<T::BlockNumber, OldBalance>| {
+			translated += 1;
+			Some(AccountActivity {
+				demurrage_charged_at: activity.demurrage_charged_at,
+				transfer_volume: activity.transfer_volume.map(|(era, volume)| (era, volume.into())),
+			})
+		});
+		FeeRevenue::<T, I>::translate(|_, _, revenue: OldBalance| {
+			translated += 1;
+			Some(revenue.into())
+		});
+		CurrencySnapshots::<T, I>::translate(
+			|_, snapshot: CurrencySnapshot<CurrencyIdOf<T, I>, OldBalance, T::AccountId>| {
+				translated += 1;
+				Some(CurrencySnapshot {
+					currency_id: snapshot.currency_id,
+					total_issuance: snapshot.total_issuance.into(),
+					treasury_balance: snapshot.treasury_balance.into(),
+					slash_destination: snapshot.slash_destination,
+					demurrage_rate: snapshot.demurrage_rate,
+					savings_rate: snapshot.savings_rate,
+					volume_tracked: snapshot.volume_tracked,
+				})
+			},
+		);
+		Holds::<T, I>::translate(|_, _, holds: Vec<(T::HoldReason, OldBalance)>| {
+			translated += 1;
+			Some(holds.into_iter().map(|(reason, amount)| (reason, amount.into())).collect())
+		});
+		Freezes::<T, I>::translate(|_, _, freezes: Vec<BalanceLock<OldBalance>>| {
+			translated += 1;
+			Some(
+				freezes
+					.into_iter()
+					.map(|freeze| BalanceLock {
+						id: freeze.id,
+						amount: freeze.amount.into(),
+					})
+					.collect(),
+			)
+		});
+		ReserveExpiryQueue::<T, I>::translate(
+			|_, expiring: Vec<(T::AccountId, CurrencyIdOf<T, I>, OldBalance)>| {
+				translated += 1;
+				Some(
+					expiring
+						.into_iter()
+						.map(|(who, currency_id, amount)| (who, currency_id, amount.into()))
+						.collect(),
+				)
+			},
+		);
+		Delegations::<T, I>::translate(|_, _, delegation: Delegation<OldBalance>| {
+			translated += 1;
+			Some(Delegation {
+				max_per_period: delegation.max_per_period.into(),
+				era: delegation.era,
+				spent: delegation.spent.into(),
+			})
+		});
+		TransferGuards::<T, I>::translate(|_, _, (guardian, threshold): (T::AccountId, OldBalance)| {
+			translated += 1;
+			Some((guardian, threshold.into()))
+		});
+		PendingTransfers::<T, I>::translate(
+			|_, transfer: PendingTransfer<T::AccountId, CurrencyIdOf<T, I>, OldBalance, T::BlockNumber>| {
+				translated += 1;
+				Some(PendingTransfer {
+					from: transfer.from,
+					to: transfer.to,
+					guardian: transfer.guardian,
+					currency_id: transfer.currency_id,
+					amount: transfer.amount.into(),
+					expires_at: transfer.expires_at,
+				})
+			},
+		);
+		BalanceCheckpoints::<T, I>::translate(|_, _, checkpoints: Vec<(T::BlockNumber, OldBalance)>| {
+			translated += 1;
+			Some(
+				checkpoints
+					.into_iter()
+					.map(|(block, balance)| (block, balance.into()))
+					.collect(),
+			)
+		});
+		IssuanceHistory::<T, I>::translate(|_, history: Vec<(T::BlockNumber, OldBalance)>| {
+			translated += 1;
+			Some(history.into_iter().map(|(block, amount)| (block, amount.into())).collect())
+		});
+		AccumulatedDust::<T, I>::translate(|_, dust: OldBalance| {
+			translated += 1;
+			Some(dust.into())
+		});
+
+		StorageVersion::<T, I>::put(Releases::V3);
+
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+
+	/// The number of raw entries stored under `prefix`.
+	#[cfg(feature = "try-runtime")]
+	fn count_entries(prefix: &[u8]) -> u32 {
+		let mut count = 0;
+		let mut key = prefix.to_vec();
+		while let Some(next) = sp_io::storage::next_key(&key) {
+			if !next.starts_with(prefix) {
+				break;
+			}
+			count += 1;
+			key = next;
+		}
+		count
+	}
+
+	/// The sum of the raw entries stored under `prefix`, decoded as
+	/// `OldBalance`.
+	#[cfg(feature = "try-runtime")]
+	fn sum_raw_entries<T, I, OldBalance>(prefix: &[u8]) -> BalanceOf<T, I>
+	where
+		T: Config<I>,
+		I: 'static,
+		OldBalance: Decode + Into<BalanceOf<T, I>>,
+	{
+		let mut sum = Zero::zero();
+		let mut key = prefix.to_vec();
+		while let Some(next) = sp_io::storage::next_key(&key) {
+			if !next.starts_with(prefix) {
+				break;
+			}
+			if let Some(value) = unhashed::get::<OldBalance>(&next) {
+				sum = value.into().saturating_add(sum);
+			}
+			key = next;
+		}
+		sum
+	}
+
+	/// The sum of the plain balances the migration translates, decoded as
+	/// `OldBalance`.
+	#[cfg(feature = "try-runtime")]
+	fn raw_balance_sum<T, I, OldBalance>() -> BalanceOf<T, I>
+	where
+		T: Config<I>,
+		I: 'static,
+		OldBalance: Decode + Into<BalanceOf<T, I>>,
+	{
+		sum_raw_entries::<T, I, OldBalance>(&TotalLocked::<T, I>::final_prefix())
+			.saturating_add(sum_raw_entries::<T, I, OldBalance>(&TotalReserved::<T, I>::final_prefix()))
+			.saturating_add(sum_raw_entries::<T, I, OldBalance>(&FeeRevenue::<T, I>::final_prefix()))
+			.saturating_add(sum_raw_entries::<T, I, OldBalance>(&AccumulatedDust::<T, I>::final_prefix()))
+			.saturating_add(
+				unhashed::get::<OldBalance>(&NextEraIssuance::<T, I>::hashed_key())
+					.map(Into::into)
+					.unwrap_or_else(Zero::zero),
+			)
+	}
+
+	/// The sum of the plain balances the migration translates, decoded with
+	/// the new types.
+	#[cfg(feature = "try-runtime")]
+	fn balance_sum<T: Config<I>, I: 'static>() -> BalanceOf<T, I> {
+		TotalLocked::<T, I>::iter_values()
+			.chain(TotalReserved::<T, I>::iter_values())
+			.chain(FeeRevenue::<T, I>::iter_values())
+			.chain(AccumulatedDust::<T, I>::iter_values())
+			.fold(NextEraIssuance::<T, I>::get(), |sum, value| sum.saturating_add(value))
+	}
+
+	/// The number of entries the migration translates, as raw entries.
+	#[cfg(feature = "try-runtime")]
+	fn raw_entries<T: Config<I>, I: 'static>() -> u32 {
+		count_entries(&TotalLocked::<T, I>::final_prefix())
+			+ count_entries(&TotalReserved::<T, I>::final_prefix())
+			+ count_entries(&Locks::<T, I>::final_prefix())
+			+ count_entries(&PendingUpdates::<T, I>::final_prefix())
+			+ count_entries(&SupplyHistory::<T, I>::final_prefix())
+			+ count_entries(&SerpParameters::<T, I>::final_prefix())
+			+ NativeIssuanceSchedule::<T, I>::exists() as u32
+			+ NextEraIssuance::<T, I>::exists() as u32
+			+ count_entries(&Savings::<T, I>::final_prefix())
+			+ count_entries(&AccountActivities::<T, I>::final_prefix())
+			+ count_entries(&FeeRevenue::<T, I>::final_prefix())
+			+ count_entries(&CurrencySnapshots::<T, I>::final_prefix())
+			+ count_entries(&Holds::<T, I>::final_prefix())
+			+ count_entries(&Freezes::<T, I>::final_prefix())
+			+ count_entries(&ReserveExpiryQueue::<T, I>::final_prefix())
+			+ count_entries(&Delegations::<T, I>::final_prefix())
+			+ count_entries(&TransferGuards::<T, I>::final_prefix())
+			+ count_entries(&PendingTransfers::<T, I>::final_prefix())
+			+ count_entries(&BalanceCheckpoints::<T, I>::final_prefix())
+			+ count_entries(&IssuanceHistory::<T, I>::final_prefix())
+			+ count_entries(&AccumulatedDust::<T, I>::final_prefix())
+	}
+
+	/// The number of entries the migration translates that decode with the
+	/// new types.
+	#[cfg(feature = "try-runtime")]
+	fn decodable_entries<T: Config<I>, I: 'static>() -> u32 {
+		(TotalLocked::<T, I>::iter().count()
+			+ TotalReserved::<T, I>::iter().count()
+			+ Locks::<T, I>::iter().count()
+			+ PendingUpdates::<T, I>::iter().count()
+			+ SupplyHistory::<T, I>::iter().count()
+			+ SerpParameters::<T, I>::iter().count()
+			+ NativeIssuanceSchedule::<T, I>::try_get().is_ok() as usize
+			+ NextEraIssuance::<T, I>::try_get().is_ok() as usize
+			+ Savings::<T, I>::iter().count()
+			+ AccountActivities::<T, I>::iter().count()
+			+ FeeRevenue::<T, I>::iter().count()
+			+ CurrencySnapshots::<T, I>::iter().count()
+			+ Holds::<T, I>::iter().count()
+			+ Freezes::<T, I>::iter().count()
+			+ ReserveExpiryQueue::<T, I>::iter().count()
+			+ Delegations::<T, I>::iter().count()
+			+ TransferGuards::<T, I>::iter().count()
+			+ PendingTransfers::<T, I>::iter().count()
+			+ BalanceCheckpoints::<T, I>::iter().count()
+			+ IssuanceHistory::<T, I>::iter().count()
+			+ AccumulatedDust::<T, I>::iter().count()) as u32
+	}
+
+	/// Record the number of entries to translate and the sum of the plain
+	/// balances among them.
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_upgrade<T, I, OldBalance>() -> Result<(), &'static str>
+	where
+		T: Config<I>,
+		I: 'static,
+		OldBalance: Decode + Into<BalanceOf<T, I>>,
+	{
+		match StorageVersion::<T, I>::get() {
+			Releases::V1 => Err("MigrateToV2 must run before widening balances"),
+			Releases::V2 => {
+				unhashed::put(ENTRIES_KEY, &raw_entries::<T, I>());
+				unhashed::put(SUM_KEY, &raw_balance_sum::<T, I, OldBalance>());
+				Ok(())
+			}
+			Releases::V3 => Ok(()),
+		}
+	}
+
+	/// Check that every entry was translated and decodes with the new types,
+	/// and that the plain balances add up to what they did before.
+	#[cfg(feature = "try-runtime")]
+	pub fn post_upgrade<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
+		if StorageVersion::<T, I>::get() != Releases::V3 {
+			return Err("storage version was not updated to V3");
+		}
+		let entries: u32 = match unhashed::take(ENTRIES_KEY) {
+			Some(entries) => entries,
+			// already widened by an earlier upgrade
+			None => return Ok(()),
+		};
+		let sum: BalanceOf<T, I> = unhashed::take(SUM_KEY).ok_or("pre_upgrade did not run")?;
+		if balance_sum::<T, I>() != sum {
+			return Err("balances changed while widening balances");
+		}
+		if raw_entries::<T, I>() != entries {
+			return Err("entries were lost while widening balances");
+		}
+		if decodable_entries::<T, I>() != entries {
+			return Err("entries do not decode after widening balances");
+		}
+		Ok(())
+	}
+}
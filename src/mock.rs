@@ -1,15 +1,18 @@
 //! Mocks for the Stp258 currencies module.
-
-#![cfg(test)]
+//!
+//! Exported with the `test-utils` feature so that pallets built on
+//! `Stp258Currency` can test against a working runtime.
 
 use super::*;
-use frame_support::{construct_runtime, parameter_types};
+use frame_support::{assert_ok, construct_runtime, instances::Instance1, ord_parameter_types, parameter_types};
+use frame_system::{EnsureOneOf, EnsureRoot, EnsureSignedBy};
 use serp_traits::parameter_type_with_key;
 use sp_core::H256;
+use sp_std::cell::RefCell;
 use sp_runtime::{
 	testing::Header,
 	traits::{AccountIdConversion, IdentityLookup},
-	AccountId32, ModuleId, Perbill,
+	AccountId32, ModuleId, MultiSignature, MultiSigner, Perbill,
 };
 
 use crate as stp258_standard;
@@ -44,12 +47,44 @@ impl frame_system::Config for Runtime {
 	type SS58Prefix = ();
 }
 
-type CurrencyId = u32;
-type Balance = u64;
-type Blocknumber = u64;
+pub type CurrencyId = u32;
+pub type Balance = u64;
+pub type Blocknumber = u64;
 
-parameter_types! {
-	pub const ExistentialDeposit: u64 = 1;
+thread_local! {
+	static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(1);
+	static RELEASE_FALLBACK: RefCell<ReleaseFallback> = RefCell::new(ReleaseFallback::KeepReserved);
+	static EXISTENTIAL_DEPOSITS: RefCell<Vec<(CurrencyId, Balance)>> = RefCell::new(Vec::new());
+}
+
+/// The native existential deposit, 1 unless changed with `set`.
+pub struct ExistentialDeposit;
+
+impl ExistentialDeposit {
+	pub fn set(amount: u64) {
+		EXISTENTIAL_DEPOSIT.with(|ed| *ed.borrow_mut() = amount);
+	}
+}
+
+impl Get<u64> for ExistentialDeposit {
+	fn get() -> u64 {
+		EXISTENTIAL_DEPOSIT.with(|ed| *ed.borrow())
+	}
+}
+
+/// The `Config::ReleaseFallback`, `KeepReserved` unless changed with `set`.
+pub struct GetReleaseFallback;
+
+impl GetReleaseFallback {
+	pub fn set(fallback: ReleaseFallback) {
+		RELEASE_FALLBACK.with(|f| *f.borrow_mut() = fallback);
+	}
+}
+
+impl Get<ReleaseFallback> for GetReleaseFallback {
+	fn get() -> ReleaseFallback {
+		RELEASE_FALLBACK.with(|f| *f.borrow())
+	}
 }
 
 impl pallet_balances::Config for Runtime {
@@ -64,10 +99,26 @@ impl pallet_balances::Config for Runtime {
 
 parameter_type_with_key! {
 	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
-		Default::default()
+		EXISTENTIAL_DEPOSITS.with(|eds| {
+			eds.borrow()
+				.iter()
+				.find(|(id, _)| id == currency_id)
+				.map(|(_, ed)| *ed)
+				.unwrap_or_default()
+		})
 	};
 }
 
+/// Set the existential deposit of the non-native `currency_id`, 0 unless
+/// set.
+pub fn set_existential_deposit(currency_id: CurrencyId, amount: Balance) {
+	EXISTENTIAL_DEPOSITS.with(|eds| {
+		let mut eds = eds.borrow_mut();
+		eds.retain(|(id, _)| *id != currency_id);
+		eds.push((currency_id, amount));
+	});
+}
+
 parameter_type_with_key! {
 	pub GetBaseUnit: |currency_id: CurrencyId| -> Balance {
 		match currency_id {
@@ -85,7 +136,7 @@ const SERPER_RATIO: Perbill = Perbill::from_percent(25);
 const SETT_PAY_RATIO: Perbill = Perbill::from_percent(75);
 
 parameter_types! {
-	pub DustAccount: AccountId = ModuleId(*b"dsss/dst").into_account();
+	pub TreasuryAccount: AccountId = ModuleId(*b"set/trsy").into_account();
 }
 
 parameter_types! {
@@ -117,7 +168,7 @@ impl stp258_serp::Config for Runtime {
 	type GetSerperRatio = GetSerperRatio;
 	type GetSettPayRatio = GetSettPayRatio;
 	type GetSingleUnit = GetSingleUnit;
-	type OnDust = stp258_serp::TransferDust<Runtime, DustAccount>;
+	type OnDust = AggregateDust<Runtime>;
 }
 
 pub const DNAR: CurrencyId = 1;
@@ -125,9 +176,51 @@ pub const SETT: CurrencyId = 2;
 pub const JUSD: CurrencyId = 3;
 
 pub const ADJUSTMENT_FREQUENCY: Blocknumber = 10;
+pub const UPDATE_DELAY: Blocknumber = 5;
+pub const ERA_LENGTH: Blocknumber = 10;
 
 parameter_types! {
 	pub const GetStp258NativeId: CurrencyId = DNAR;
+	pub const UpdateDelay: Blocknumber = UPDATE_DELAY;
+	pub const EraLength: Blocknumber = ERA_LENGTH;
+	pub const MaxSupplyHistory: u32 = 3;
+	pub const MaxLockExpiriesPerBlock: u32 = 5;
+	pub const MaxLocks: u32 = 10;
+	pub const TransferConfirmationWindow: Blocknumber = 5;
+	pub const MaxReserveExpiriesPerBlock: u32 = 3;
+	pub const MaxIssuanceBeneficiaries: u32 = 3;
+	pub const MaxMetadataLength: u32 = 32;
+	pub const MaxCheckpoints: u32 = 4;
+	pub const MaxBalancesPerQuery: u32 = 3;
+	pub SampledCurrencies: Vec<CurrencyId> = vec![DNAR, SETT, JUSD];
+	pub const MaxIssuanceSamples: u32 = 3;
+	pub const FeeRevenueRetention: u32 = 2;
+	pub const Stp258ModuleId: ModuleId = ModuleId(*b"stp/2580");
+	pub const DustModuleId: ModuleId = ModuleId(*b"stp/dust");
+	pub const GetDustDestination: DustDestination = DustDestination::Treasury;
+	pub const MaxDustRedistributionsPerBlock: u32 = 1;
+}
+
+ord_parameter_types! {
+	pub const MonetaryMember: AccountId = AccountId32::new([6u8; 32]);
+	pub const ComplianceMember: AccountId = AccountId32::new([7u8; 32]);
+	pub const SerpMember: AccountId = AccountId32::new([8u8; 32]);
+}
+
+/// Root, or the single signed `Member`, so each privileged origin is also
+/// held by an account the others reject.
+pub type EnsureRootOr<Member> = EnsureOneOf<AccountId, EnsureRoot<AccountId>, EnsureSignedBy<Member, AccountId>>;
+
+/// The reasons balance is held for in the mock runtime.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Serialize, Deserialize)]
+pub enum HoldReason {
+	Escrow,
+	Bond,
+	Expiry,
+}
+
+parameter_types! {
+	pub const ReserveExpiryHoldReason: HoldReason = HoldReason::Expiry;
 }
 
 impl Config for Runtime {
@@ -135,14 +228,98 @@ impl Config for Runtime {
 	type Stp258Currency = Stp258Serp;
 	type Stp258Native = AdaptedStp258Asset;
 	type GetStp258NativeId = GetStp258NativeId;
+	type MonetaryOrigin = EnsureRootOr<MonetaryMember>;
+	type ComplianceOrigin = EnsureRootOr<ComplianceMember>;
+	type EmergencyOrigin = EnsureRoot<AccountId>;
+	type SerpOrigin = EnsureRootOr<SerpMember>;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type MaxSupplyHistory = MaxSupplyHistory;
+	type MaxLockExpiriesPerBlock = MaxLockExpiriesPerBlock;
+	type MaxLocks = MaxLocks;
+	type MaxReserveExpiriesPerBlock = MaxReserveExpiriesPerBlock;
+	type MaxIssuanceBeneficiaries = MaxIssuanceBeneficiaries;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxCheckpoints = MaxCheckpoints;
+	type MaxBalancesPerQuery = MaxBalancesPerQuery;
+	type SampledCurrencies = SampledCurrencies;
+	type MaxIssuanceSamples = MaxIssuanceSamples;
+	type FeeRevenueRetention = FeeRevenueRetention;
+	type EraLength = EraLength;
+	type UpdateDelay = UpdateDelay;
+	type TransferConfirmationWindow = TransferConfirmationWindow;
+	type TreasuryAccount = TreasuryAccount;
+	type SerperAccount = GetSerperAcc;
+	type ModuleId = Stp258ModuleId;
+	type DustModuleId = DustModuleId;
+	type DustDestination = GetDustDestination;
+	type MaxDustRedistributionsPerBlock = MaxDustRedistributionsPerBlock;
+	type ReleaseFallback = GetReleaseFallback;
+	type HoldReason = HoldReason;
+	type ReserveExpiryHoldReason = ReserveExpiryHoldReason;
+	type AuthorizationSignature = MultiSignature;
+	type AuthorizationSigner = MultiSigner;
+	type OnDeposit = RecordingFlows;
+	type OnWithdraw = RecordingFlows;
+	type OnSlash = RecordingFlows;
+	type Metrics = RecordingMetrics;
+	type WeightInfo = ();
+}
+parameter_types! {
+	pub const Stp258ModuleId1: ModuleId = ModuleId(*b"stp/2581");
+	pub const DustModuleId1: ModuleId = ModuleId(*b"stp/dus1");
+}
+
+/// A second instance over the same backing currencies, so free balances
+/// are shared while the module's own storage and derived accounts are not.
+impl Config<Instance1> for Runtime {
+	type Event = Event;
+	type Stp258Currency = Stp258Serp;
+	type Stp258Native = AdaptedStp258Asset;
+	type GetStp258NativeId = GetStp258NativeId;
+	type MonetaryOrigin = EnsureRoot<AccountId>;
+	type ComplianceOrigin = EnsureRoot<AccountId>;
+	type EmergencyOrigin = EnsureRoot<AccountId>;
+	type SerpOrigin = EnsureRoot<AccountId>;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type MaxSupplyHistory = MaxSupplyHistory;
+	type MaxLockExpiriesPerBlock = MaxLockExpiriesPerBlock;
+	type MaxLocks = MaxLocks;
+	type MaxReserveExpiriesPerBlock = MaxReserveExpiriesPerBlock;
+	type MaxIssuanceBeneficiaries = MaxIssuanceBeneficiaries;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxCheckpoints = MaxCheckpoints;
+	type MaxBalancesPerQuery = MaxBalancesPerQuery;
+	type SampledCurrencies = SampledCurrencies;
+	type MaxIssuanceSamples = MaxIssuanceSamples;
+	type FeeRevenueRetention = FeeRevenueRetention;
+	type EraLength = EraLength;
+	type UpdateDelay = UpdateDelay;
+	type TransferConfirmationWindow = TransferConfirmationWindow;
+	type TreasuryAccount = TreasuryAccount;
+	type SerperAccount = GetSerperAcc;
+	type ModuleId = Stp258ModuleId1;
+	type DustModuleId = DustModuleId1;
+	type DustDestination = GetDustDestination;
+	type MaxDustRedistributionsPerBlock = MaxDustRedistributionsPerBlock;
+	type ReleaseFallback = GetReleaseFallback;
+	type HoldReason = HoldReason;
+	type ReserveExpiryHoldReason = ReserveExpiryHoldReason;
+	type AuthorizationSignature = MultiSignature;
+	type AuthorizationSigner = MultiSigner;
+	type OnDeposit = ();
+	type OnWithdraw = ();
+	type OnSlash = ();
+	type Metrics = ();
 	type WeightInfo = ();
 }
+
 pub type Stp258Native = Stp258NativeOf<Runtime>;
 pub type AdaptedStp258Asset = Stp258AssetAdapter<Runtime, PalletBalances, i64, u64>;
 
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
-type Block = frame_system::mocking::MockBlock<Runtime>;
+pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+pub type Block = frame_system::mocking::MockBlock<Runtime>;
 
+#[cfg(not(feature = "dev"))]
 construct_runtime!(
 	pub enum Runtime where
 		Block = Block,
@@ -150,12 +327,33 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Storage, Config, Event<T>},
-		Stp258Standard: stp258_standard::{Module, Call, Event<T>},
+		Stp258Standard: stp258_standard::{Module, Call, Storage, Event<T>, Config<T>},
+		Stp258StandardInstance1: stp258_standard::<Instance1>::{Module, Call, Storage, Event<T>},
 		Stp258Serp: stp258_serp::{Module, Storage, Event<T>, Config<T>},
 		PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
+// the dev calls cannot be declared conditionally within one runtime
+#[cfg(feature = "dev")]
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Storage, Config, Event<T>},
+		Stp258Standard: stp258_standard::{Module, Call, Storage, Event<T>, Config<T>},
+		Stp258StandardInstance1: stp258_standard::<Instance1>::{Module, Call, Storage, Event<T>},
+		Stp258Serp: stp258_serp::{Module, Storage, Event<T>, Config<T>},
+		PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		Stp258Dev: stp258_standard::dev::{Module, Call, Storage},
+	}
+);
+
+#[cfg(feature = "dev")]
+impl dev::Config for Runtime {}
+
 
 pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
@@ -228,3 +426,316 @@ impl ExtBuilder {
 		t.into()
 	}
 }
+
+thread_local! {
+	static METRICS: RefCell<Vec<(&'static str, CurrencyId)>> = RefCell::new(Vec::new());
+}
+
+/// Records the counters reported through `Config::Metrics`.
+pub struct RecordingMetrics;
+
+impl Metrics<CurrencyId> for RecordingMetrics {
+	fn on_transfer(currency_id: CurrencyId) {
+		METRICS.with(|m| m.borrow_mut().push(("transfer", currency_id)));
+	}
+
+	fn on_serp_action(currency_id: CurrencyId) {
+		METRICS.with(|m| m.borrow_mut().push(("serp_action", currency_id)));
+	}
+
+	fn on_failed_withdrawal(currency_id: CurrencyId) {
+		METRICS.with(|m| m.borrow_mut().push(("failed_withdrawal", currency_id)));
+	}
+}
+
+/// The counters recorded by `RecordingMetrics` on this thread.
+pub fn recorded_metrics() -> Vec<(&'static str, CurrencyId)> {
+	METRICS.with(|m| m.borrow().clone())
+}
+
+thread_local! {
+	static FLOWS: RefCell<Vec<(&'static str, CurrencyId, AccountId, Balance)>> = RefCell::new(Vec::new());
+}
+
+/// Records the deposits, withdrawals and slashes observed through
+/// `Config::OnDeposit`, `Config::OnWithdraw` and `Config::OnSlash`.
+pub struct RecordingFlows;
+
+impl OnDeposit<AccountId, CurrencyId, Balance> for RecordingFlows {
+	fn on_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		FLOWS.with(|f| f.borrow_mut().push(("deposit", currency_id, who.clone(), amount)));
+	}
+}
+
+impl OnWithdraw<AccountId, CurrencyId, Balance> for RecordingFlows {
+	fn on_withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		FLOWS.with(|f| f.borrow_mut().push(("withdraw", currency_id, who.clone(), amount)));
+	}
+}
+
+impl OnSlash<AccountId, CurrencyId, Balance> for RecordingFlows {
+	fn on_slash(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		FLOWS.with(|f| f.borrow_mut().push(("slash", currency_id, who.clone(), amount)));
+	}
+}
+
+/// The flows recorded by `RecordingFlows` on this thread.
+pub fn recorded_flows() -> Vec<(&'static str, CurrencyId, AccountId, Balance)> {
+	FLOWS.with(|f| f.borrow().clone())
+}
+
+thread_local! {
+	static LOGS: RefCell<Vec<(log::Level, String)>> = RefCell::new(Vec::new());
+}
+
+/// Records the messages logged under the module's log target.
+pub struct RecordingLogger;
+
+static LOGGER: RecordingLogger = RecordingLogger;
+
+impl log::Log for RecordingLogger {
+	fn enabled(&self, metadata: &log::Metadata) -> bool {
+		metadata.target() == LOG_TARGET
+	}
+
+	fn log(&self, record: &log::Record) {
+		if self.enabled(record.metadata()) {
+			LOGS.with(|l| l.borrow_mut().push((record.level(), record.args().to_string())));
+		}
+	}
+
+	fn flush(&self) {}
+}
+
+/// Install `RecordingLogger`, recording warnings and errors on this thread.
+pub fn record_logs() {
+	let _ = log::set_logger(&LOGGER);
+	log::set_max_level(log::LevelFilter::Warn);
+}
+
+/// The messages recorded by `RecordingLogger` on this thread.
+pub fn recorded_logs() -> Vec<(log::Level, String)> {
+	LOGS.with(|l| l.borrow().clone())
+}
+
+/// Deposit `amount` of `currency_id` into `who`.
+pub fn endow(who: &AccountId, currency_id: CurrencyId, amount: Balance) {
+	assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::deposit(currency_id, who, amount));
+}
+
+/// Assert that `event` was deposited in the current block.
+pub fn assert_event(event: Event) {
+	assert!(System::events().iter().any(|record| record.event == event));
+}
+
+/// The last event deposited by the Stp258Standard module, if any.
+pub fn last_stp258_event() -> Option<crate::Event<Runtime>> {
+	System::events().into_iter().rev().find_map(|record| match record.event {
+		Event::stp258_standard(event) => Some(event),
+		_ => None,
+	})
+}
+
+/// Assert that `amount` of `currency_id` was transferred from `from` to
+/// `to` in the current block.
+pub fn assert_transferred(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) {
+	assert!(System::events().iter().any(|record| matches!(
+		&record.event,
+		Event::stp258_standard(crate::Event::Transferred(c, f, t, a, ..))
+			if *c == currency_id && f == from && t == to && *a == amount
+	)));
+}
+
+/// Assert that `amount` of `currency_id` was deposited into `who` in the
+/// current block.
+pub fn assert_deposited(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+	assert!(System::events().iter().any(|record| matches!(
+		&record.event,
+		Event::stp258_standard(crate::Event::Deposited(c, w, a, _))
+			if *c == currency_id && w == who && *a == amount
+	)));
+}
+
+/// Assert that `amount` of `currency_id` was withdrawn from `who` in the
+/// current block.
+pub fn assert_withdrawn(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+	assert!(System::events().iter().any(|record| matches!(
+		&record.event,
+		Event::stp258_standard(crate::Event::Withdrawn(c, w, a, _))
+			if *c == currency_id && w == who && *a == amount
+	)));
+}
+
+/// A runtime resolving call destinations from `MultiAddress`, for testing
+/// the calls the way wallets using account indices reach them. The other
+/// tests use `Runtime`, whose `IdentityLookup` only takes account ids.
+pub mod indexed {
+	use super::*;
+	use sp_runtime::{
+		traits::{LookupError, StaticLookup},
+		MultiAddress,
+	};
+
+	/// The accounts with an index, as `pallet_indices` would assign them:
+	/// index `i` is the `i`th account.
+	pub const INDEXED_ACCOUNTS: [AccountId; 2] = [ALICE, BOB];
+
+	/// Resolves `Id`, `Index` (through `INDEXED_ACCOUNTS`), `Address32` and
+	/// `Raw` encoded account ids. A 32-byte account has no 20-byte address,
+	/// so `Address20` does not resolve.
+	pub struct MultiAddressLookup;
+
+	impl StaticLookup for MultiAddressLookup {
+		type Source = MultiAddress<AccountId, u32>;
+		type Target = AccountId;
+
+		fn lookup(source: Self::Source) -> Result<AccountId, LookupError> {
+			match source {
+				MultiAddress::Id(who) => Ok(who),
+				MultiAddress::Index(index) => INDEXED_ACCOUNTS.get(index as usize).cloned().ok_or(LookupError),
+				MultiAddress::Address32(bytes) => Ok(AccountId32::new(bytes)),
+				MultiAddress::Raw(bytes) => AccountId::decode(&mut &bytes[..]).map_err(|_| LookupError),
+				MultiAddress::Address20(_) => Err(LookupError),
+			}
+		}
+
+		fn unlookup(who: AccountId) -> Self::Source {
+			MultiAddress::Id(who)
+		}
+	}
+
+	impl frame_system::Config for IndexedRuntime {
+		type Origin = Origin;
+		type Call = Call;
+		type Index = u64;
+		type BlockNumber = Blocknumber;
+		type Hash = H256;
+		type Hashing = ::sp_runtime::traits::BlakeTwo256;
+		type AccountId = AccountId;
+		type Lookup = MultiAddressLookup;
+		type Header = Header;
+		type Event = Event;
+		type BlockHashCount = BlockHashCount;
+		type BlockWeights = ();
+		type BlockLength = ();
+		type Version = ();
+		type PalletInfo = PalletInfo;
+		type AccountData = pallet_balances::AccountData<u64>;
+		type OnNewAccount = ();
+		type OnKilledAccount = ();
+		type DbWeight = ();
+		type BaseCallFilter = ();
+		type SystemWeightInfo = ();
+		type SS58Prefix = ();
+	}
+
+	impl pallet_balances::Config for IndexedRuntime {
+		type Balance = Balance;
+		type DustRemoval = ();
+		type Event = Event;
+		type ExistentialDeposit = ExistentialDeposit;
+		type AccountStore = frame_system::Module<IndexedRuntime>;
+		type MaxLocks = ();
+		type WeightInfo = ();
+	}
+
+	impl stp258_serp::Config for IndexedRuntime {
+		type Event = Event;
+		type Balance = Balance;
+		type Amount = i64;
+		type CurrencyId = CurrencyId;
+		type WeightInfo = ();
+		type ExistentialDeposits = ExistentialDeposits;
+		type GetBaseUnit = GetBaseUnit;
+		type AdjustmentFrequency = AdjustmentFrequency;
+		type GetPercent = GetPercent;
+		type GetSerpNativeId = GetSerpNativeId;
+		type GetSerpQuoteMultiple = GetSerpQuoteMultiple;
+		type GetSerperAcc = GetSerperAcc;
+		type GetSettPayAcc = GetSettPayAcc;
+		type GetSerperRatio = GetSerperRatio;
+		type GetSettPayRatio = GetSettPayRatio;
+		type GetSingleUnit = GetSingleUnit;
+		type OnDust = AggregateDust<IndexedRuntime>;
+	}
+
+	impl Config for IndexedRuntime {
+		type Event = Event;
+		type Stp258Currency = Stp258Serp;
+		type Stp258Native = Stp258AssetAdapter<IndexedRuntime, PalletBalances, i64, u64>;
+		type GetStp258NativeId = GetStp258NativeId;
+		type MonetaryOrigin = EnsureRoot<AccountId>;
+		type ComplianceOrigin = EnsureRoot<AccountId>;
+		type EmergencyOrigin = EnsureRoot<AccountId>;
+		type SerpOrigin = EnsureRoot<AccountId>;
+		type GovernanceOrigin = EnsureRoot<AccountId>;
+		type MaxSupplyHistory = MaxSupplyHistory;
+		type MaxLockExpiriesPerBlock = MaxLockExpiriesPerBlock;
+		type MaxLocks = MaxLocks;
+		type MaxReserveExpiriesPerBlock = MaxReserveExpiriesPerBlock;
+		type MaxIssuanceBeneficiaries = MaxIssuanceBeneficiaries;
+		type MaxMetadataLength = MaxMetadataLength;
+		type MaxCheckpoints = MaxCheckpoints;
+		type MaxBalancesPerQuery = MaxBalancesPerQuery;
+		type SampledCurrencies = SampledCurrencies;
+		type MaxIssuanceSamples = MaxIssuanceSamples;
+		type FeeRevenueRetention = FeeRevenueRetention;
+		type EraLength = EraLength;
+		type UpdateDelay = UpdateDelay;
+		type TransferConfirmationWindow = TransferConfirmationWindow;
+		type TreasuryAccount = TreasuryAccount;
+		type SerperAccount = GetSerperAcc;
+		type ModuleId = Stp258ModuleId;
+		type DustModuleId = DustModuleId;
+		type DustDestination = GetDustDestination;
+		type MaxDustRedistributionsPerBlock = MaxDustRedistributionsPerBlock;
+		type ReleaseFallback = GetReleaseFallback;
+		type HoldReason = HoldReason;
+		type ReserveExpiryHoldReason = ReserveExpiryHoldReason;
+		type AuthorizationSignature = MultiSignature;
+		type AuthorizationSigner = MultiSigner;
+		type OnDeposit = ();
+		type OnWithdraw = ();
+		type OnSlash = ();
+		type Metrics = ();
+		type WeightInfo = ();
+	}
+
+	pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<IndexedRuntime>;
+	pub type Block = frame_system::mocking::MockBlock<IndexedRuntime>;
+
+	construct_runtime!(
+		pub enum IndexedRuntime where
+			Block = Block,
+			NodeBlock = Block,
+			UncheckedExtrinsic = UncheckedExtrinsic,
+		{
+			System: frame_system::{Module, Call, Storage, Config, Event<T>},
+			Stp258Standard: stp258_standard::{Module, Call, Storage, Event<T>, Config<T>},
+			Stp258Serp: stp258_serp::{Module, Storage, Event<T>, Config<T>},
+			PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		}
+	);
+
+	/// Externalities with 100 `DNAR` and 100 `SETT` units for the indexed
+	/// accounts.
+	pub fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<IndexedRuntime>()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<IndexedRuntime> {
+			balances: INDEXED_ACCOUNTS.iter().map(|who| (who.clone(), 100)).collect(),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		stp258_serp::GenesisConfig::<IndexedRuntime> {
+			endowed_accounts: INDEXED_ACCOUNTS.iter().map(|who| (who.clone(), SETT, 100 * 10_000)).collect(),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		t.into()
+	}
+}
@@ -0,0 +1,78 @@
+//! Runtime API definition for the Stp258Standard module.
+
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use crate::SubAccount;
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait Stp258StandardApi<AccountId, CurrencyId, Balance, BlockNumber> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// The free balance of `who` under `currency_id` that is not
+		/// restricted by locks.
+		fn usable_balance(currency_id: CurrencyId, who: AccountId) -> Balance;
+
+		/// The total balance of `currency_id` held by locks.
+		fn total_locked(currency_id: CurrencyId) -> Balance;
+
+		/// The total balance of `currency_id` reserved.
+		fn total_reserved(currency_id: CurrencyId) -> Balance;
+
+		/// The block of the next SERP adjustment of `currency_id`, if one is
+		/// scheduled.
+		fn next_adjustment_block(currency_id: CurrencyId) -> Option<BlockNumber>;
+
+		/// The free balances of `accounts` under `currency_id`, in order,
+		/// for at most the pallet's `MaxBalancesPerQuery` accounts.
+		fn balances_of(currency_id: CurrencyId, accounts: Vec<AccountId>) -> Vec<Balance>;
+
+		/// The total balance of `who` under `currency_id` at the end of
+		/// `block`, if `currency_id` is checkpointed and the checkpoint kept.
+		fn balance_at(currency_id: CurrencyId, who: AccountId, block: BlockNumber) -> Option<Balance>;
+
+		/// The sampled total issuance of `currency_id`, oldest first.
+		fn issuance_history(currency_id: CurrencyId) -> Vec<(BlockNumber, Balance)>;
+
+		/// The total issuance of `currency_id` outside registered protocol
+		/// sub-accounts and locks.
+		fn circulating_supply(currency_id: CurrencyId) -> Balance;
+
+		/// The active protocol sub-accounts and their accounts.
+		fn sub_accounts() -> Vec<(SubAccount, AccountId)>;
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// ERC-20 shaped answers per currency, for EVM-side tooling and
+	/// precompiles.
+	pub trait Erc20CompatApi<AccountId, CurrencyId, Balance> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec,
+	{
+		/// The name of `currency_id`, if set.
+		fn name(currency_id: CurrencyId) -> Option<Vec<u8>>;
+
+		/// The symbol of `currency_id`, if set.
+		fn symbol(currency_id: CurrencyId) -> Option<Vec<u8>>;
+
+		/// The decimals of `currency_id`, if its base unit is a power of ten.
+		fn decimals(currency_id: CurrencyId) -> Option<u32>;
+
+		/// The total issuance of `currency_id`.
+		fn total_supply(currency_id: CurrencyId) -> Balance;
+
+		/// The free balance of `who` under `currency_id`.
+		fn balance_of(currency_id: CurrencyId, who: AccountId) -> Balance;
+
+		/// What `spender` may still transfer of `owner`'s `currency_id` in
+		/// the current era.
+		fn allowance(currency_id: CurrencyId, owner: AccountId, spender: AccountId) -> Balance;
+	}
+}
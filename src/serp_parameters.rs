@@ -0,0 +1,98 @@
+//! On-chain SERP parameters.
+//!
+//! The `Get` implementations in this module read a parameter from
+//! `SerpParameters` and fall back to a compile-time default, so a runtime
+//! can plug them into the SERP configuration and tune it with
+//! `set_serp_parameter`.
+
+use crate::{BalanceOf, Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{Perbill, RuntimeDebug};
+use sp_std::marker;
+
+/// Identifies a SERP parameter.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SerpParameterKey {
+	AdjustmentFrequency,
+	SerpQuoteMultiple,
+	SerperRatio,
+	SettPayRatio,
+}
+
+/// A SERP parameter and its value.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SerpParameter<BlockNumber, Balance> {
+	/// The number of blocks between SERP adjustments.
+	AdjustmentFrequency(BlockNumber),
+	/// The multiple of the adjustment quoted to serpers.
+	SerpQuoteMultiple(Balance),
+	/// The share of an adjustment going to the serper.
+	SerperRatio(Perbill),
+	/// The share of an adjustment going to the SettPay account.
+	SettPayRatio(Perbill),
+}
+
+impl<BlockNumber, Balance> SerpParameter<BlockNumber, Balance> {
+	/// The key this parameter is stored under.
+	pub fn key(&self) -> SerpParameterKey {
+		match self {
+			SerpParameter::AdjustmentFrequency(_) => SerpParameterKey::AdjustmentFrequency,
+			SerpParameter::SerpQuoteMultiple(_) => SerpParameterKey::SerpQuoteMultiple,
+			SerpParameter::SerperRatio(_) => SerpParameterKey::SerperRatio,
+			SerpParameter::SettPayRatio(_) => SerpParameterKey::SettPayRatio,
+		}
+	}
+}
+
+/// The `AdjustmentFrequency` parameter, `DefaultValue` if unset.
+pub struct AdjustmentFrequency<T, DefaultValue, I = ()>(marker::PhantomData<(T, DefaultValue, I)>);
+
+impl<T: Config<I>, I: 'static, DefaultValue: Get<T::BlockNumber>> Get<T::BlockNumber> for AdjustmentFrequency<T, DefaultValue, I> {
+	fn get() -> T::BlockNumber {
+		match <Pallet<T, I>>::serp_parameter(SerpParameterKey::AdjustmentFrequency) {
+			Some(SerpParameter::AdjustmentFrequency(frequency)) => frequency,
+			_ => DefaultValue::get(),
+		}
+	}
+}
+
+/// The `SerpQuoteMultiple` parameter, `DefaultValue` if unset.
+pub struct SerpQuoteMultiple<T, DefaultValue, I = ()>(marker::PhantomData<(T, DefaultValue, I)>);
+
+impl<T: Config<I>, I: 'static, DefaultValue: Get<BalanceOf<T, I>>> Get<BalanceOf<T, I>> for SerpQuoteMultiple<T, DefaultValue, I> {
+	fn get() -> BalanceOf<T, I> {
+		match <Pallet<T, I>>::serp_parameter(SerpParameterKey::SerpQuoteMultiple) {
+			Some(SerpParameter::SerpQuoteMultiple(multiple)) => multiple,
+			_ => DefaultValue::get(),
+		}
+	}
+}
+
+/// The `SerperRatio` parameter, `DefaultValue` if unset.
+pub struct SerperRatio<T, DefaultValue, I = ()>(marker::PhantomData<(T, DefaultValue, I)>);
+
+impl<T: Config<I>, I: 'static, DefaultValue: Get<Perbill>> Get<Perbill> for SerperRatio<T, DefaultValue, I> {
+	fn get() -> Perbill {
+		match <Pallet<T, I>>::serp_parameter(SerpParameterKey::SerperRatio) {
+			Some(SerpParameter::SerperRatio(ratio)) => ratio,
+			_ => DefaultValue::get(),
+		}
+	}
+}
+
+/// The `SettPayRatio` parameter, `DefaultValue` if unset.
+pub struct SettPayRatio<T, DefaultValue, I = ()>(marker::PhantomData<(T, DefaultValue, I)>);
+
+impl<T: Config<I>, I: 'static, DefaultValue: Get<Perbill>> Get<Perbill> for SettPayRatio<T, DefaultValue, I> {
+	fn get() -> Perbill {
+		match <Pallet<T, I>>::serp_parameter(SerpParameterKey::SettPayRatio) {
+			Some(SerpParameter::SettPayRatio(ratio)) => ratio,
+			_ => DefaultValue::get(),
+		}
+	}
+}
@@ -0,0 +1,55 @@
+//! SS58 rendering of the module's protocol accounts.
+//!
+//! For simulation and state-dump tooling, so operators can match on-chain
+//! accounts to the ones this module is configured with.
+
+use super::*;
+use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
+
+/// The module's protocol accounts, by name.
+///
+/// The treasury and serper accounts, then the registered sub-accounts, the
+/// derived deposit accounts and the dust accounts. Every currency has a dust
+/// account, so only those of the non-native `SampledCurrencies` are listed;
+/// native dust is not aggregated.
+pub fn protocol_accounts<T: Config<I>, I: 'static>() -> Vec<(String, T::AccountId)> {
+	let mut accounts = vec![
+		("treasury".into(), T::TreasuryAccount::get()),
+		("serper".into(), T::SerperAccount::get()),
+	];
+	accounts.extend(
+		Pallet::<T, I>::sub_accounts()
+			.into_iter()
+			.map(|(sub_account, who)| (format!("sub_account/{:?}", sub_account), who)),
+	);
+	accounts.extend(
+		DepositAccounts::<T, I>::iter()
+			.map(|(who, account)| (format!("deposit/{:?}/{}", account.parent, account.tag), who)),
+	);
+	let native_currency_id = Pallet::<T, I>::native_currency_id();
+	accounts.extend(
+		T::SampledCurrencies::get()
+			.into_iter()
+			.filter(|currency_id| *currency_id != native_currency_id)
+			.map(|currency_id| (format!("dust/{:?}", currency_id), Pallet::<T, I>::dust_account(currency_id))),
+	);
+	accounts
+}
+
+/// `who` as SS58 with address `prefix`.
+pub fn to_ss58<A: Ss58Codec>(who: &A, prefix: u8) -> String {
+	who.to_ss58check_with_version(Ss58AddressFormat::Custom(prefix))
+}
+
+/// The module's protocol accounts, by name, as SS58 with the runtime's
+/// `SS58Prefix`.
+pub fn protocol_accounts_ss58<T: Config<I>, I: 'static>() -> Vec<(String, String)>
+where
+	T::AccountId: Ss58Codec,
+{
+	let prefix = T::SS58Prefix::get();
+	protocol_accounts::<T, I>()
+		.into_iter()
+		.map(|(name, who)| (name, to_ss58(&who, prefix)))
+		.collect()
+}
@@ -0,0 +1,63 @@
+//! JSON snapshots of the module's storage.
+//!
+//! For forensic analysis of a chain's state and for seeding simulations
+//! from it. Run `export_state` inside the externalities to dump, e.g.
+//! `ext.execute_with(export_state::<Runtime, ()>)`.
+
+use super::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// All storage owned by the module, as JSON.
+pub fn export_state<T: Config<I>, I: 'static>() -> Value
+where
+	CurrencyIdOf<T, I>: Serialize,
+	BalanceOf<T, I>: Serialize,
+	AmountOf<T, I>: Serialize,
+{
+	json!({
+		"storage_version": StorageVersion::<T, I>::get(),
+		"native_currency_id_override": NativeCurrencyIdOverride::<T, I>::get(),
+		"serp_parameters": SerpParameters::<T, I>::iter_values().collect::<Vec<_>>(),
+		"slash_destinations": SlashDestinations::<T, I>::iter().collect::<Vec<_>>(),
+		"slash_orders": SlashOrders::<T, I>::iter().collect::<Vec<_>>(),
+		"next_update_id": NextUpdateId::<T, I>::get(),
+		"pending_updates": PendingUpdates::<T, I>::iter().collect::<Vec<_>>(),
+		"lock_prefixes": LockPrefixes::<T, I>::iter().collect::<Vec<_>>(),
+		"locks": Locks::<T, I>::iter().collect::<Vec<_>>(),
+		"total_locked": TotalLocked::<T, I>::iter().collect::<Vec<_>>(),
+		"total_reserved": TotalReserved::<T, I>::iter().collect::<Vec<_>>(),
+		"lock_expiry": LockExpiry::<T, I>::iter().collect::<Vec<_>>(),
+		"lock_expiry_queue": LockExpiryQueue::<T, I>::iter().collect::<Vec<_>>(),
+		"supply_history": SupplyHistory::<T, I>::iter().collect::<Vec<_>>(),
+		"operation_nonces": OperationNonces::<T, I>::iter().collect::<Vec<_>>(),
+		"deposit_accounts": DepositAccounts::<T, I>::iter().collect::<Vec<_>>(),
+		"sub_accounts": SubAccounts::<T, I>::iter().collect::<Vec<_>>(),
+		"accumulated_dust": AccumulatedDust::<T, I>::iter().collect::<Vec<_>>(),
+		"dust_redistribution_pass": DustRedistributionPass::<T, I>::get(),
+		"issuance_history": IssuanceHistory::<T, I>::iter().collect::<Vec<_>>(),
+		"issuance_schedule": NativeIssuanceSchedule::<T, I>::get(),
+		"issuance_beneficiaries": IssuanceBeneficiaries::<T, I>::get(),
+		"next_era_issuance": NextEraIssuance::<T, I>::get(),
+		"demurrage_rates": DemurrageRates::<T, I>::iter().collect::<Vec<_>>(),
+		"account_activities": AccountActivities::<T, I>::iter().collect::<Vec<_>>(),
+		"savings_rates": SavingsRates::<T, I>::iter().collect::<Vec<_>>(),
+		"savings_indices": SavingsIndices::<T, I>::iter().collect::<Vec<_>>(),
+		"savings": Savings::<T, I>::iter().collect::<Vec<_>>(),
+		"volume_tracked": VolumeTracked::<T, I>::iter().collect::<Vec<_>>(),
+		"fee_split": FeeSplitConfig::<T, I>::get(),
+		"fee_revenue": FeeRevenue::<T, I>::iter().collect::<Vec<_>>(),
+		"checkpointed_since": CheckpointedSince::<T, I>::iter().collect::<Vec<_>>(),
+		"balance_checkpoints": BalanceCheckpoints::<T, I>::iter().collect::<Vec<_>>(),
+		"currency_metadata": CurrencyMetadatas::<T, I>::iter().collect::<Vec<_>>(),
+		"transfer_guards": TransferGuards::<T, I>::iter().collect::<Vec<_>>(),
+		"next_transfer_id": NextTransferId::<T, I>::get(),
+		"pending_transfers": PendingTransfers::<T, I>::iter().collect::<Vec<_>>(),
+		"authorization_nonces": AuthorizationNonces::<T, I>::iter().collect::<Vec<_>>(),
+		"delegations": Delegations::<T, I>::iter().collect::<Vec<_>>(),
+		"reserve_expiry_queue": ReserveExpiryQueue::<T, I>::iter().collect::<Vec<_>>(),
+		"freezes": Freezes::<T, I>::iter().collect::<Vec<_>>(),
+		"holds": Holds::<T, I>::iter().collect::<Vec<_>>(),
+		"currency_snapshots": CurrencySnapshots::<T, I>::iter().collect::<Vec<_>>(),
+	})
+}
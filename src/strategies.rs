@@ -0,0 +1,25 @@
+//! Proptest strategies over the mock runtime.
+//!
+//! Exported with the `test-utils` feature, next to `mock`, so that pallets
+//! built on `Stp258Currency` can generate inputs valid against it.
+
+use crate::mock::{Balance, CurrencyId, DNAR, JUSD, SETT};
+use proptest::prelude::*;
+
+/// A mock currency with the balance each account is endowed with by
+/// `one_hundred_for_alice_n_bob_n_serper_n_settpay`.
+pub fn endowed_currency() -> impl Strategy<Value = (CurrencyId, Balance)> {
+	prop_oneof![
+		Just((DNAR, 100)),
+		Just((SETT, 100 * 10_000)),
+		Just((JUSD, 100 * 1_000)),
+	]
+}
+
+/// A `(currency_id, balance, amount)` triple where `amount` never takes an
+/// account endowed with `balance` below the existential deposit.
+pub fn currency_balance_amount() -> impl Strategy<Value = (CurrencyId, Balance, i64)> {
+	endowed_currency().prop_flat_map(|(currency_id, balance)| {
+		(Just(currency_id), Just(balance), -(balance as i64 - 1)..=1_000_000_000i64)
+	})
+}
@@ -3,9 +3,15 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GenesisBuild, OnInitialize, OnRuntimeUpgrade},
+};
 use mock::{Event, *};
-use sp_runtime::traits::BadOrigin;
+use proptest::prelude::*;
+use strategies::{currency_balance_amount, endowed_currency};
+use sp_core::{sr25519, Pair, H256};
+use sp_runtime::{traits::BadOrigin, MultiSignature, MultiSigner};
 
 #[test]
 fn stp258_currency_lockable_should_work() {
@@ -241,6 +247,28 @@ fn update_balance_call_fails_if_not_root_origin() {
 	});
 }
 
+#[test]
+fn update_balance_call_should_accept_the_configured_origin() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				Stp258Standard::update_balance(Origin::signed(ComplianceMember::get()), ALICE, SETT, 10 * 10_000),
+				BadOrigin
+			);
+			assert_ok!(Stp258Standard::update_balance(
+				Origin::signed(MonetaryMember::get()),
+				ALICE,
+				SETT,
+				10 * 10_000
+			));
+			assert_event(Event::stp258_standard(crate::Event::BalanceUpdated(SETT, ALICE, 10 * 10_000, 1)));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 110 * 10_000);
+		});
+}
+
 #[test]
 fn call_event_should_work() {
 	ExtBuilder::default()
@@ -253,8 +281,8 @@ fn call_event_should_work() {
 			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 50 * 10_000);
 			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 150 * 10_000);
 
-			let transferred_event = Event::stp258_standard(crate::Event::Transferred(SETT, ALICE, BOB, 50 * 10_000));
-			assert!(System::events().iter().any(|record| record.event == transferred_event));
+			let transferred_event = Event::stp258_standard(crate::Event::Transferred(SETT, ALICE, BOB, 50 * 10_000, 1, 1));
+			assert_event(transferred_event);
 
 			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(
 				SETT, &ALICE, &BOB, 10 * 10_000
@@ -262,23 +290,2655 @@ fn call_event_should_work() {
 			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 40 * 10_000);
 			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 160 * 10_000);
 
-			let transferred_event = Event::stp258_standard(crate::Event::Transferred(SETT, ALICE, BOB, 10 * 10_000));
-			assert!(System::events().iter().any(|record| record.event == transferred_event));
+			let transferred_event = Event::stp258_standard(crate::Event::Transferred(SETT, ALICE, BOB, 10 * 10_000, 2, 2));
+			assert_event(transferred_event);
 
 			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::deposit(
 				SETT, &ALICE, 100 * 10_000
 			));
 			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 140 * 10_000);
 
-			let transferred_event = Event::stp258_standard(crate::Event::Deposited(SETT, ALICE, 100 * 10_000));
-			assert!(System::events().iter().any(|record| record.event == transferred_event));
+			let transferred_event = Event::stp258_standard(crate::Event::Deposited(SETT, ALICE, 100 * 10_000, 3));
+			assert_event(transferred_event);
 
 			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(
 				SETT, &ALICE, 20 * 10_000
 			));
 			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 120 * 10_000);
 
-			let transferred_event = Event::stp258_standard(crate::Event::Withdrawn(SETT, ALICE, 20 * 10_000));
-			assert!(System::events().iter().any(|record| record.event == transferred_event));
+			let transferred_event = Event::stp258_standard(crate::Event::Withdrawn(SETT, ALICE, 20 * 10_000, 4));
+			assert_event(transferred_event);
+		});
+}
+
+#[test]
+fn event_helpers_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_eq!(last_stp258_event(), None);
+
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 50 * 10_000));
+			assert_transferred(SETT, &ALICE, &BOB, 50 * 10_000);
+
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::deposit(SETT, &ALICE, 10 * 10_000));
+			assert_deposited(SETT, &ALICE, 10 * 10_000);
+
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(SETT, &ALICE, 20 * 10_000));
+			assert_withdrawn(SETT, &ALICE, 20 * 10_000);
+			assert_eq!(
+				last_stp258_event(),
+				Some(crate::Event::Withdrawn(SETT, ALICE, 20 * 10_000, 3))
+			);
+		});
+}
+
+#[test]
+fn announced_update_should_execute_after_delay() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Standard::announce_update(Origin::root(), ALICE, SETT, 10 * 10_000));
+			assert_eq!(
+				Stp258Standard::pending_updates(0).map(|update| update.unlock_at),
+				Some(1 + UPDATE_DELAY)
+			);
+			assert_noop!(
+				Stp258Standard::execute_update(Some(BOB).into(), 0),
+				Error::<Runtime>::UpdateNotUnlocked
+			);
+
+			System::set_block_number(1 + UPDATE_DELAY);
+			assert_ok!(Stp258Standard::execute_update(Some(BOB).into(), 0));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 110 * 10_000);
+			assert_eq!(Stp258Standard::pending_updates(0), None);
+
+			let executed_event = Event::stp258_standard(crate::Event::UpdateExecuted(0));
+			assert_event(executed_event);
+		});
+}
+
+#[test]
+fn storage_types_should_round_trip_through_serde() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::announce_update(Origin::root(), ALICE, SETT, -10 * 10_000));
+			assert_ok!(Stp258Standard::deposit_to_savings(Some(BOB).into(), SETT, 10 * 10_000));
+
+			let update = Stp258Standard::pending_updates(0).unwrap();
+			let json = serde_json::to_string(&update).unwrap();
+			assert_eq!(serde_json::from_str::<PendingUpdateOf<Runtime>>(&json).unwrap(), update);
+
+			let savings = Stp258Standard::savings(&BOB, SETT);
+			assert_eq!(savings.principal, 10 * 10_000);
+			let json = serde_json::to_string(&savings).unwrap();
+			assert_eq!(serde_json::from_str::<SavingsBalance<Balance>>(&json).unwrap(), savings);
+
+			let key = SerpParameterKey::SerperRatio;
+			let json = serde_json::to_string(&key).unwrap();
+			assert_eq!(serde_json::from_str::<SerpParameterKey>(&json).unwrap(), key);
+		});
+}
+
+#[test]
+fn vetoed_update_should_not_execute() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::announce_update(Origin::root(), ALICE, DNAR, -10));
+			assert_noop!(Stp258Standard::veto_update(Some(BOB).into(), 0), BadOrigin);
+			assert_ok!(Stp258Standard::veto_update(Origin::root(), 0));
+			assert_eq!(Stp258Standard::pending_updates(0), None);
+
+			System::set_block_number(1 + UPDATE_DELAY);
+			assert_noop!(
+				Stp258Standard::execute_update(Some(BOB).into(), 0),
+				Error::<Runtime>::UpdateNotFound
+			);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 100);
+		});
+}
+
+#[test]
+fn announce_update_fails_if_not_monetary_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Stp258Standard::announce_update(Some(ALICE).into(), ALICE, SETT, 100 * 10_000),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn slash_should_follow_slash_destination() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Standard::set_slash_destination(
+				Origin::root(),
+				DNAR,
+				SlashDestination::Treasury
+			));
+			assert_eq!(Stp258Standard::slash(DNAR, &ALICE, 10), 0);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 90);
+			assert_eq!(Stp258Native::free_balance(&TreasuryAccount::get()), 10);
+			assert_eq!(Stp258Native::total_issuance(), 400);
+
+			let slashed_event = Event::stp258_standard(crate::Event::Slashed(
+				DNAR,
+				ALICE,
+				10,
+				SlashDestination::Treasury,
+				Some(SlashOrder::FreeFirst),
+				1,
+			));
+			assert_event(slashed_event);
+
+			assert_ok!(Stp258Standard::set_slash_destination(
+				Origin::root(),
+				SETT,
+				SlashDestination::Split(Perbill::from_percent(50))
+			));
+			assert_ok!(Stp258Standard::reserve(SETT, &ALICE, 20 * 10_000));
+			assert_eq!(Stp258Standard::slash_reserved(SETT, &ALICE, 20 * 10_000), 0);
+			assert_eq!(Stp258Standard::free_balance(SETT, &TreasuryAccount::get()), 10 * 10_000);
+			assert_eq!(Stp258Standard::total_issuance(SETT), 390 * 10_000);
+		});
+}
+
+#[test]
+fn privileged_calls_should_require_their_own_origin() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let monetary = || Origin::signed(MonetaryMember::get());
+			let compliance = || Origin::signed(ComplianceMember::get());
+			let serp = || Origin::signed(SerpMember::get());
+
+			assert_noop!(
+				Stp258Standard::set_slash_destination(compliance(), SETT, SlashDestination::Treasury),
+				BadOrigin
+			);
+			assert_noop!(
+				Stp258Standard::set_slash_destination(serp(), SETT, SlashDestination::Treasury),
+				BadOrigin
+			);
+			assert_ok!(Stp258Standard::set_slash_destination(monetary(), SETT, SlashDestination::Treasury));
+
+			assert_noop!(Stp258Standard::register_lock_prefix(monetary(), *b"stk"), BadOrigin);
+			assert_noop!(Stp258Standard::register_lock_prefix(serp(), *b"stk"), BadOrigin);
+			assert_ok!(Stp258Standard::register_lock_prefix(compliance(), *b"stk"));
+
+			let frequency = SerpParameter::AdjustmentFrequency(20);
+			assert_noop!(Stp258Standard::set_serp_parameter(monetary(), frequency), BadOrigin);
+			assert_noop!(Stp258Standard::set_serp_parameter(compliance(), frequency), BadOrigin);
+			assert_ok!(Stp258Standard::set_serp_parameter(serp(), frequency));
+
+			assert_ok!(Stp258Standard::announce_update(monetary(), ALICE, DNAR, -10));
+			assert_noop!(Stp258Standard::veto_update(monetary(), 0), BadOrigin);
+			assert_ok!(Stp258Standard::veto_update(Origin::root(), 0));
+		});
+}
+
+#[test]
+fn set_slash_destination_fails_if_not_monetary_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Stp258Standard::set_slash_destination(Some(ALICE).into(), SETT, SlashDestination::Account(BOB)),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn inspect_accounts_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::announce_update(Origin::root(), ALICE, SETT, 10 * 10_000));
+			assert_ok!(Stp258Standard::announce_update(Origin::root(), BOB, DNAR, 10));
+			assert_ok!(Stp258Standard::set_slash_destination(
+				Origin::root(),
+				SETT,
+				SlashDestination::Account(BOB)
+			));
+
+			assert_eq!(Stp258Standard::all_pending_updates().len(), 2);
+			let updates_of_bob = Stp258Standard::pending_updates_of(&BOB);
+			assert_eq!(updates_of_bob.len(), 1);
+			assert_eq!(updates_of_bob[0].0, 1);
+			assert_eq!(updates_of_bob[0].1.currency_id, DNAR);
+			assert_eq!(
+				Stp258Standard::slash_destinations(),
+				vec![(SETT, SlashDestination::Account(BOB))]
+			);
+		});
+}
+
+#[test]
+fn conversions_should_work() {
+	assert_eq!(conversions::amount_to_balance_checked::<i64, u64>(-10), Some(10));
+	assert_eq!(conversions::amount_to_balance_checked::<i64, u64>(i64::min_value()), None);
+	assert_eq!(conversions::balance_to_amount_checked::<i64, u64>(u64::max_value()), None);
+	assert_eq!(
+		conversions::balance_to_amount_saturating::<i64, u64>(u64::max_value()),
+		i64::max_value()
+	);
+	assert_eq!(conversions::balance_delta::<i64, u64>(30, 10), Some(-20));
+	assert_eq!(conversions::apply_amount::<i64, u64>(30, -20), Some(10));
+	assert_eq!(conversions::apply_amount::<i64, u64>(10, -20), None);
+}
+
+#[test]
+fn transfer_returning_actual_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_eq!(Stp258Standard::transfer_returning_actual(DNAR, &ALICE, &BOB, 30), Ok(30));
+			assert_eq!(Stp258Native::free_balance(&BOB), 130);
+			assert_eq!(Stp258Standard::transfer_returning_actual(SETT, &ALICE, &ALICE, 30), Ok(0));
+
+			let transferred_event = Event::stp258_standard(crate::Event::Transferred(DNAR, ALICE, BOB, 30, 1, 1));
+			assert_event(transferred_event);
+		});
+}
+
+#[test]
+fn deposit_into_existing_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stp258Standard::deposit_into_existing(SETT, &TreasuryAccount::get(), 10 * 10_000),
+				Error::<Runtime>::DeadAccount
+			);
+			assert_ok!(Stp258Standard::deposit_into_existing(SETT, &ALICE, 10 * 10_000));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 110 * 10_000);
+
+			assert_noop!(
+				Stp258Native::deposit_into_existing(&TreasuryAccount::get(), 10),
+				Error::<Runtime>::DeadAccount
+			);
+			assert!(AdaptedStp258Asset::deposit_into_existing(&TreasuryAccount::get(), 10).is_err());
+			assert_ok!(AdaptedStp258Asset::deposit_into_existing(&ALICE, 10));
+			assert_eq!(PalletBalances::total_balance(&ALICE), 110);
+		});
+}
+
+#[test]
+fn usable_balance_should_account_for_locks() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::set_lock(ID_1, DNAR, &ALICE, 30));
+			assert_eq!(Stp258Standard::usable_balance(DNAR, &ALICE), 70);
+			assert_ok!(Stp258Standard::extend_lock(ID_1, DNAR, &ALICE, 50));
+			assert_eq!(Stp258Native::usable_balance(&ALICE), 50);
+			assert_ok!(Stp258Standard::remove_lock(ID_1, DNAR, &ALICE));
+			assert_eq!(Stp258Standard::usable_balance(DNAR, &ALICE), 100);
+			assert!(Stp258Standard::locks(&ALICE, DNAR).is_empty());
+
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &ALICE, 60 * 10_000));
+			assert_eq!(Stp258Standard::usable_balance(SETT, &ALICE), 40 * 10_000);
+			assert_eq!(
+				Stp258Standard::locks(&ALICE, SETT),
+				vec![BalanceLock {
+					id: ID_1,
+					amount: 60 * 10_000
+				}]
+			);
+		});
+}
+
+#[test]
+fn can_withdraw_should_report_consequence() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				Stp258Standard::can_withdraw(DNAR, &ALICE, 50),
+				WithdrawConsequence::Success(50)
+			);
+			assert_eq!(Stp258Standard::can_withdraw(DNAR, &ALICE, 500), WithdrawConsequence::Underflow);
+			assert_eq!(Stp258Standard::can_withdraw(DNAR, &ALICE, 101), WithdrawConsequence::NoFunds);
+			assert_eq!(Stp258Native::can_withdraw(&ALICE, 100), WithdrawConsequence::WouldDie);
+
+			assert_ok!(Stp258Standard::set_lock(ID_1, DNAR, &ALICE, 80));
+			assert_eq!(Stp258Standard::can_withdraw(DNAR, &ALICE, 30), WithdrawConsequence::Frozen);
+
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &ALICE, 80 * 10_000));
+			assert_eq!(
+				Stp258Standard::can_withdraw(SETT, &ALICE, 30 * 10_000),
+				WithdrawConsequence::Frozen
+			);
+			assert_eq!(
+				Stp258Standard::can_withdraw(SETT, &ALICE, 20 * 10_000),
+				WithdrawConsequence::Success(80 * 10_000)
+			);
+		});
+}
+
+#[test]
+fn namespaced_lock_id_should_work() {
+	let lock_id = lock_id::namespaced(*b"stk", 7);
+	assert_eq!(lock_id, [b'#', b's', b't', b'k', 7, 0, 0, 0]);
+	assert_eq!(lock_id::prefix_of(&lock_id), Some(*b"stk"));
+	assert_eq!(lock_id::prefix_of(&ID_1), None);
+}
+
+#[test]
+fn set_lock_should_check_lock_prefix() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let lock_id = lock_id::namespaced(*b"stk", 1);
+			assert_noop!(
+				Stp258Standard::set_lock(lock_id, SETT, &ALICE, 10 * 10_000),
+				Error::<Runtime>::UnregisteredLockPrefix
+			);
+
+			assert_ok!(Stp258Standard::register_lock_prefix(Origin::root(), *b"stk"));
+			assert_noop!(
+				Stp258Standard::register_lock_prefix(Origin::root(), *b"stk"),
+				Error::<Runtime>::LockPrefixAlreadyRegistered
+			);
+			assert_ok!(Stp258Standard::set_lock(lock_id, SETT, &ALICE, 10 * 10_000));
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &ALICE, 20 * 10_000));
+			assert_eq!(Stp258Serp::locks(&ALICE, SETT).len(), 2);
+
+			assert_ok!(Stp258Standard::deregister_lock_prefix(Origin::root(), *b"stk"));
+			assert_noop!(
+				Stp258Standard::extend_lock(lock_id, SETT, &ALICE, 30 * 10_000),
+				Error::<Runtime>::UnregisteredLockPrefix
+			);
+		});
+}
+
+#[test]
+fn lock_expiries_per_block_are_bounded() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(benchmark_helpers::fill_lock_expiry_queue::<Runtime, ()>(
+				SETT,
+				&ALICE,
+				10,
+				3,
+				MaxLockExpiriesPerBlock::get()
+			));
+			assert_noop!(
+				Stp258Standard::set_lock_until(ID_1, SETT, &BOB, 10, 3),
+				Error::<Runtime>::TooManyLockExpiries
+			);
+			assert_ok!(Stp258Standard::set_lock_until(ID_1, SETT, &BOB, 10, 4));
+		});
+}
+
+#[test]
+fn reserve_until_should_release() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				Stp258Standard::reserve_until(SETT, &ALICE, 10, 1),
+				Error::<Runtime>::InvalidReserveExpiry
+			);
+			assert_ok!(Stp258Standard::reserve_until(SETT, &ALICE, 10, 3));
+			assert_ok!(Stp258Standard::reserve_until(SETT, &BOB, 20, 3));
+			assert_ok!(Stp258Standard::reserve_until(SETT, &BOB, 20, 3));
+			assert_noop!(
+				Stp258Standard::reserve_until(SETT, &BOB, 20, 3),
+				Error::<Runtime>::TooManyReserveExpiries
+			);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &BOB), 40);
+			assert_eq!(Stp258Standard::balance_on_hold(HoldReason::Expiry, SETT, &BOB), 40);
+
+			// plain unreserve leaves the scheduled reserve alone
+			assert_eq!(Stp258Standard::unreserve(SETT, &ALICE, 4), 4);
+			// part of Alice's hold is released early
+			assert_eq!(Stp258Standard::release(HoldReason::Expiry, SETT, &ALICE, 4), 4);
+
+			Stp258Standard::on_initialize(3);
+			assert_event(Event::stp258_standard(crate::Event::ReserveExpired(SETT, ALICE, 6)));
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 0);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &BOB), 0);
+			assert!(Stp258Standard::reserve_expiry_queue(3).is_empty());
+		});
+}
+
+#[test]
+fn expired_reserve_should_not_release_other_reserves() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::reserve(SETT, &ALICE, 5));
+			assert_ok!(Stp258Standard::hold(HoldReason::Escrow, SETT, &ALICE, 7));
+			assert_ok!(Stp258Standard::reserve_until(SETT, &ALICE, 10, 3));
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 22);
+
+			// the scheduled reserve is released before it expires
+			assert_eq!(Stp258Standard::release(HoldReason::Expiry, SETT, &ALICE, 10), 10);
+
+			Stp258Standard::on_initialize(3);
+			assert_event(Event::stp258_standard(crate::Event::ReserveExpired(SETT, ALICE, 0)));
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 12);
+			assert_eq!(Stp258Standard::balance_on_hold(HoldReason::Escrow, SETT, &ALICE), 7);
+		});
+}
+
+#[test]
+fn locks_by_amount_should_name_binding_lock() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let vest: LockIdentifier = *b"vest    ";
+			assert_eq!(Stp258Standard::locks_by_amount(SETT, &ALICE), (vec![], None));
+
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &ALICE, 10));
+			assert_ok!(Stp258Standard::set_lock(vest, SETT, &ALICE, 30));
+			assert_eq!(
+				Stp258Standard::locks_by_amount(SETT, &ALICE),
+				(
+					vec![BalanceLock { id: vest, amount: 30 }, BalanceLock { id: ID_1, amount: 10 }],
+					Some(vest)
+				)
+			);
+		});
+}
+
+#[test]
+fn locks_per_account_are_bounded() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(benchmark_helpers::set_max_locks::<Runtime, ()>(
+				SETT,
+				&ALICE,
+				10,
+				MaxLocks::get()
+			));
+			assert_eq!(Stp258Standard::lock_count(SETT, &ALICE), MaxLocks::get());
+			assert_noop!(
+				Stp258Standard::set_lock(ID_1, SETT, &ALICE, 10),
+				Error::<Runtime>::TooManyLocks
+			);
+			assert_noop!(
+				Stp258Standard::extend_lock(ID_1, SETT, &ALICE, 10),
+				Error::<Runtime>::TooManyLocks
+			);
+			// existing locks can still be changed
+			assert_ok!(Stp258Standard::set_lock(benchmark_helpers::benchmark_lock_id(0), SETT, &ALICE, 20));
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &BOB, 10));
+		});
+}
+
+#[test]
+fn set_lock_until_should_expire() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Stp258Standard::set_lock_until(ID_1, DNAR, &ALICE, 50, 1),
+				Error::<Runtime>::InvalidLockExpiry
+			);
+			assert_ok!(Stp258Standard::set_lock_until(ID_1, DNAR, &ALICE, 50, 3));
+			assert_ok!(Stp258Standard::set_lock_until(ID_1, SETT, &ALICE, 50 * 10_000, 3));
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &ALICE, 60 * 10_000));
+			assert_eq!(Stp258Standard::lock_expiry(&ALICE, (DNAR, ID_1)), Some(3));
+			assert_eq!(Stp258Standard::lock_expiry(&ALICE, (SETT, ID_1)), None);
+
+			<Stp258Standard as OnInitialize<u64>>::on_initialize(2);
+			assert_eq!(PalletBalances::locks(&ALICE).len(), 1);
+
+			<Stp258Standard as OnInitialize<u64>>::on_initialize(3);
+			assert_eq!(PalletBalances::locks(&ALICE).len(), 0);
+			assert_eq!(Stp258Standard::usable_balance(DNAR, &ALICE), 100);
+			assert_eq!(Stp258Serp::locks(&ALICE, SETT).len(), 1);
+
+			let expired_event = Event::stp258_standard(crate::Event::LockExpired(DNAR, ALICE, ID_1));
+			assert_event(expired_event);
+		});
+}
+
+#[test]
+fn total_locked_and_reserved_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::set_lock(ID_1, DNAR, &ALICE, 30));
+			assert_ok!(Stp258Standard::set_lock(ID_1, DNAR, &BOB, 20));
+			assert_eq!(Stp258Standard::total_locked(DNAR), 50);
+			assert_ok!(Stp258Standard::extend_lock(ID_1, DNAR, &ALICE, 40));
+			assert_eq!(Stp258Standard::total_locked(DNAR), 60);
+			assert_ok!(Stp258Standard::remove_lock(ID_1, DNAR, &BOB));
+			assert_eq!(Stp258Standard::total_locked(DNAR), 40);
+
+			assert_ok!(Stp258Standard::reserve(SETT, &ALICE, 30 * 10_000));
+			assert_ok!(Stp258Standard::reserve(SETT, &BOB, 10 * 10_000));
+			assert_eq!(Stp258Standard::total_reserved(SETT), 40 * 10_000);
+			assert_eq!(Stp258Standard::unreserve(SETT, &ALICE, 10 * 10_000), 0);
+			assert_eq!(Stp258Standard::total_reserved(SETT), 30 * 10_000);
+			assert_eq!(
+				Stp258Standard::repatriate_reserved(SETT, &BOB, &ALICE, 10 * 10_000, BalanceStatus::Reserved),
+				Ok(0)
+			);
+			assert_eq!(Stp258Standard::total_reserved(SETT), 30 * 10_000);
+			assert_eq!(
+				Stp258Standard::repatriate_reserved(SETT, &ALICE, &BOB, 10 * 10_000, BalanceStatus::Free),
+				Ok(0)
+			);
+			assert_eq!(Stp258Standard::total_reserved(SETT), 20 * 10_000);
+			assert_eq!(Stp258Standard::slash_reserved(SETT, &ALICE, 5 * 10_000), 0);
+			assert_eq!(Stp258Standard::total_reserved(SETT), 15 * 10_000);
+		});
+}
+
+#[test]
+fn native_currency_id_override_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Stp258Standard::set_native_currency_id(Some(ALICE).into(), Some(JUSD)),
+				BadOrigin
+			);
+			assert_ok!(Stp258Standard::set_native_currency_id(Origin::root(), Some(JUSD)));
+			assert_eq!(Stp258Standard::native_currency_id(), JUSD);
+			assert_eq!(Stp258Standard::free_balance(JUSD, &ALICE), 100);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &ALICE), 0);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 100);
+
+			let migrated_event = Event::stp258_standard(crate::Event::NativeCurrencyIdMigrated(DNAR, JUSD));
+			assert_event(migrated_event);
+
+			// setting the current id again is a no-op
+			let events = System::events().len();
+			assert_ok!(Stp258Standard::set_native_currency_id(Origin::root(), Some(JUSD)));
+			assert_eq!(System::events().len(), events);
+
+			assert_ok!(Stp258Standard::set_native_currency_id(Origin::root(), None));
+			assert_eq!(Stp258Standard::native_currency_id(), DNAR);
+			assert_eq!(Stp258Standard::free_balance(JUSD, &ALICE), 100 * 1_000);
+
+			let events = System::events().len();
+			assert_ok!(Stp258Standard::set_native_currency_id(Origin::root(), None));
+			assert_ok!(Stp258Standard::set_native_currency_id(Origin::root(), Some(DNAR)));
+			assert_eq!(System::events().len(), events);
+			assert_eq!(Stp258Standard::native_currency_id(), DNAR);
+		});
+}
+
+#[test]
+fn set_serp_parameter_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		type Frequency = serp_parameters::AdjustmentFrequency<Runtime, AdjustmentFrequency>;
+		type Ratio = serp_parameters::SerperRatio<Runtime, GetSerperRatio>;
+
+		assert_eq!(Frequency::get(), ADJUSTMENT_FREQUENCY);
+		assert_eq!(Ratio::get(), Perbill::from_percent(25));
+
+		assert_noop!(
+			Stp258Standard::set_serp_parameter(Some(ALICE).into(), SerpParameter::AdjustmentFrequency(20)),
+			BadOrigin
+		);
+		assert_ok!(Stp258Standard::set_serp_parameter(
+			Origin::root(),
+			SerpParameter::AdjustmentFrequency(20)
+		));
+		assert_ok!(Stp258Standard::set_serp_parameter(
+			Origin::root(),
+			SerpParameter::SerperRatio(Perbill::from_percent(40))
+		));
+		assert_eq!(Frequency::get(), 20);
+		assert_eq!(Ratio::get(), Perbill::from_percent(40));
+		assert_eq!(
+			Stp258Standard::serp_parameter(SerpParameterKey::AdjustmentFrequency),
+			Some(SerpParameter::AdjustmentFrequency(20))
+		);
+	});
+}
+
+#[test]
+fn native_issuance_schedule_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let schedule = IssuanceSchedule::Decaying {
+				initial: 100,
+				decay: Perbill::from_percent(50),
+			};
+
+			assert_noop!(
+				Stp258Standard::set_issuance_schedule(
+					Origin::root(),
+					Some(schedule.clone()),
+					vec![(BOB, Perbill::from_percent(60)), (ALICE, Perbill::from_percent(60))],
+				),
+				Error::<Runtime>::InvalidIssuanceShares
+			);
+			assert_noop!(
+				Stp258Standard::set_issuance_schedule(
+					Origin::root(),
+					Some(schedule.clone()),
+					vec![(BOB, Perbill::from_percent(10)); 4],
+				),
+				Error::<Runtime>::TooManyIssuanceBeneficiaries
+			);
+			assert_ok!(Stp258Standard::set_issuance_schedule(
+				Origin::root(),
+				Some(schedule.clone()),
+				vec![(TreasuryAccount::get(), Perbill::from_percent(60)), (BOB, Perbill::from_percent(40))],
+			));
+			assert_event(Event::stp258_standard(crate::Event::IssuanceScheduleSet(Some(schedule))));
+
+			<Stp258Standard as OnInitialize<u64>>::on_initialize(ERA_LENGTH - 1);
+			assert_eq!(Stp258Standard::total_issuance(DNAR), 400);
+
+			<Stp258Standard as OnInitialize<u64>>::on_initialize(ERA_LENGTH);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &TreasuryAccount::get()), 60);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &BOB), 140);
+			assert_eq!(Stp258Standard::next_era_issuance(), 50);
+			assert_event(Event::stp258_standard(crate::Event::NativeIssued(100)));
+
+			<Stp258Standard as OnInitialize<u64>>::on_initialize(2 * ERA_LENGTH);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &TreasuryAccount::get()), 90);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &BOB), 160);
+			assert_eq!(Stp258Standard::total_issuance(DNAR), 550);
+
+			assert_ok!(Stp258Standard::set_issuance_schedule(Origin::root(), None, vec![]));
+			<Stp258Standard as OnInitialize<u64>>::on_initialize(3 * ERA_LENGTH);
+			assert_eq!(Stp258Standard::total_issuance(DNAR), 550);
+		});
+}
+
+#[test]
+fn demurrage_should_be_charged_on_touch() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+
+			// the first touch starts accruing
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 90 * 10_000);
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 110 * 10_000);
+
+			System::set_block_number(11);
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 71 * 10_000);
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 109 * 10_000);
+			assert_eq!(Stp258Standard::free_balance(SETT, &TreasuryAccount::get()), 20 * 10_000);
+			assert_event(Event::stp258_standard(crate::Event::DemurrageCharged(SETT, ALICE, 9 * 10_000)));
+
+			assert_ok!(Stp258Standard::set_demurrage_rate(Origin::root(), SETT, None));
+			System::set_block_number(21);
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 61 * 10_000);
+		});
+}
+
+#[test]
+fn demurrage_should_not_charge_locked_balance() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &ALICE, 60 * 10_000));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+
+			// only the 30 left usable accrue demurrage
+			System::set_block_number(11);
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_event(Event::stp258_standard(crate::Event::DemurrageCharged(SETT, ALICE, 3 * 10_000)));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 77 * 10_000);
+		});
+}
+
+#[test]
+fn failed_transfer_should_not_charge_demurrage() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+
+			System::set_block_number(11);
+			assert!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 100 * 10_000).is_err());
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 90 * 10_000);
+			assert_eq!(Stp258Standard::account_activity(&ALICE, SETT).demurrage_charged_at, Some(1));
+
+			// the next successful transfer is charged for all ten blocks
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_event(Event::stp258_standard(crate::Event::DemurrageCharged(SETT, ALICE, 9 * 10_000)));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 71 * 10_000);
+		});
+}
+
+#[test]
+fn demurrage_should_be_counted_like_other_withdrawals() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let treasury = TreasuryAccount::get();
+			assert_ok!(Stp258Standard::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_eq!(Stp258Standard::operation_nonce(&ALICE), 1);
+
+			System::set_block_number(11);
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_event(Event::stp258_standard(crate::Event::Withdrawn(SETT, ALICE, 9 * 10_000, 2)));
+			assert_event(Event::stp258_standard(crate::Event::Deposited(SETT, treasury.clone(), 9 * 10_000, 1)));
+			assert_event(Event::stp258_standard(crate::Event::Withdrawn(SETT, BOB, 11 * 10_000, 2)));
+			assert_event(Event::stp258_standard(crate::Event::Deposited(SETT, treasury.clone(), 11 * 10_000, 2)));
+			assert_event(Event::stp258_standard(crate::Event::Transferred(SETT, ALICE, BOB, 10 * 10_000, 3, 3)));
+			assert_eq!(Stp258Standard::operation_nonce(&treasury), 2);
+		});
+}
+
+#[test]
+fn savings_principal_should_be_withdrawn_when_interest_is_unpaid() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_savings_rate(Origin::root(), SETT, Perbill::from_percent(1)));
+			assert_ok!(Stp258Standard::deposit_to_savings(Some(ALICE).into(), SETT, 10 * 10_000));
+
+			// the treasury holds no SETT to pay the interest with
+			System::set_block_number(11);
+			assert_ok!(Stp258Standard::withdraw_from_savings(Some(ALICE).into(), SETT, 11 * 10_000));
+			assert_event(Event::stp258_standard(crate::Event::SavingsInterestUnpaid(SETT, ALICE, 10_000)));
+			assert_event(Event::stp258_standard(crate::Event::SavingsWithdrawn(SETT, ALICE, 10 * 10_000)));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 100 * 10_000);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 0);
+			assert_eq!(Stp258Standard::savings(&ALICE, SETT).principal, 0);
+			assert_eq!(Stp258Standard::savings_balance(SETT, &ALICE), 9_999);
+
+			endow(&TreasuryAccount::get(), SETT, 10_000);
+			assert_ok!(Stp258Standard::withdraw_from_savings(Some(ALICE).into(), SETT, 9_999));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 100 * 10_000 + 9_999);
+			assert_eq!(Stp258Standard::savings(&ALICE, SETT), Default::default());
+		});
+}
+
+#[test]
+fn savings_should_accrue_interest() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			endow(&TreasuryAccount::get(), SETT, 100 * 10_000);
+			assert_noop!(
+				Stp258Standard::set_savings_rate(Some(ALICE).into(), SETT, Perbill::from_percent(1)),
+				BadOrigin
+			);
+			assert_ok!(Stp258Standard::set_savings_rate(Origin::root(), SETT, Perbill::from_percent(1)));
+
+			assert_ok!(Stp258Standard::deposit_to_savings(Some(ALICE).into(), SETT, 10 * 10_000));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 90 * 10_000);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 10 * 10_000);
+
+			System::set_block_number(11);
+			assert_eq!(Stp258Standard::savings_balance(SETT, &ALICE), 11 * 10_000);
+			assert_noop!(
+				Stp258Standard::withdraw_from_savings(Some(ALICE).into(), SETT, 12 * 10_000),
+				Error::<Runtime>::InsufficientSavings
+			);
+
+			assert_ok!(Stp258Standard::withdraw_from_savings(Some(ALICE).into(), SETT, 55_000));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 95 * 10_000 + 5_000);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 5 * 10_000);
+			assert_eq!(Stp258Standard::savings_balance(SETT, &ALICE), 55_000);
+
+			assert_ok!(Stp258Standard::withdraw_from_savings(Some(ALICE).into(), SETT, 55_000));
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 101 * 10_000);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 0);
+			assert_eq!(Stp258Standard::free_balance(SETT, &TreasuryAccount::get()), 99 * 10_000);
+			assert_eq!(Stp258Standard::savings(&ALICE, SETT), Default::default());
+			assert_event(Event::stp258_standard(crate::Event::SavingsWithdrawn(SETT, ALICE, 55_000)));
+		});
+}
+
+#[test]
+fn transfer_volume_should_reset_every_era() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_eq!(<Stp258Standard as TransferVolume<AccountId>>::transfer_volume(SETT, &ALICE), 0);
+
+			assert_ok!(Stp258Standard::set_volume_tracking(Origin::root(), SETT, true));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 5 * 10_000));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, DNAR, 5));
+			assert_eq!(<Stp258Standard as TransferVolume<AccountId>>::transfer_volume(SETT, &ALICE), 15 * 10_000);
+			assert_eq!(<Stp258Standard as TransferVolume<AccountId>>::transfer_volume(SETT, &BOB), 0);
+			assert_eq!(<Stp258Standard as TransferVolume<AccountId>>::transfer_volume(DNAR, &ALICE), 0);
+
+			System::set_block_number(ERA_LENGTH);
+			assert_eq!(<Stp258Standard as TransferVolume<AccountId>>::current_era(), 1);
+			assert_eq!(<Stp258Standard as TransferVolume<AccountId>>::transfer_volume(SETT, &ALICE), 0);
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_eq!(<Stp258Standard as TransferVolume<AccountId>>::transfer_volume(SETT, &ALICE), 10 * 10_000);
+			assert_eq!(
+				Stp258Standard::account_activity(&ALICE, SETT),
+				AccountActivity {
+					demurrage_charged_at: None,
+					transfer_volume: Some((1, 10 * 10_000)),
+				}
+			);
+		});
+}
+
+#[test]
+fn account_activity_should_keep_demurrage_and_volume_together() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+			assert_ok!(Stp258Standard::set_volume_tracking(Origin::root(), SETT, true));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_eq!(
+				Stp258Standard::account_activity(&ALICE, SETT),
+				AccountActivity {
+					demurrage_charged_at: Some(1),
+					transfer_volume: Some((0, 10 * 10_000)),
+				}
+			);
+
+			// charging demurrage leaves the recorded volume in place
+			System::set_block_number(5);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::deposit(SETT, &ALICE, 10 * 10_000));
+			assert_event(Event::stp258_standard(crate::Event::DemurrageCharged(SETT, ALICE, 36_000)));
+			assert_eq!(
+				Stp258Standard::account_activity(&ALICE, SETT),
+				AccountActivity {
+					demurrage_charged_at: Some(5),
+					transfer_volume: Some((0, 10 * 10_000)),
+				}
+			);
+		});
+}
+
+#[test]
+fn fee_split_should_share_out_fee_revenue() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let fee_split = FeeSplit {
+				treasury: Perbill::from_percent(50),
+				burn: Perbill::from_percent(25),
+				serpers: Perbill::from_percent(25),
+			};
+			assert_noop!(
+				Stp258Standard::set_fee_split(
+					Origin::root(),
+					FeeSplit {
+						serpers: Perbill::from_percent(50),
+						..fee_split
+					}
+				),
+				Error::<Runtime>::InvalidFeeSplit
+			);
+			assert_ok!(Stp258Standard::set_fee_split(Origin::root(), fee_split));
+			assert_ok!(Stp258Standard::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(SETT, &ALICE, 10 * 10_000));
+			System::set_block_number(11);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(SETT, &ALICE, 10 * 10_000));
+
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 71 * 10_000);
+			assert_eq!(Stp258Standard::free_balance(SETT, &TreasuryAccount::get()), 45_000);
+			assert_eq!(Stp258Standard::free_balance(SETT, &SERPER), 100 * 10_000 + 22_500);
+			assert_eq!(Stp258Standard::total_issuance(SETT), 380 * 10_000 - 22_500);
+			assert_eq!(Stp258Standard::fee_revenue(1, SETT), 9 * 10_000);
+		});
+}
+
+#[test]
+fn fee_revenue_should_be_pruned_past_retention() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(SETT, &ALICE, 10 * 10_000));
+			System::set_block_number(11);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(SETT, &ALICE, 10 * 10_000));
+			assert_eq!(Stp258Standard::fee_revenue(1, SETT), 9 * 10_000);
+
+			// era 1 is kept while it is one of the last two
+			Stp258Standard::on_initialize(2 * ERA_LENGTH);
+			assert_eq!(Stp258Standard::fee_revenue(1, SETT), 9 * 10_000);
+			Stp258Standard::on_initialize(3 * ERA_LENGTH);
+			assert!(!FeeRevenue::<Runtime>::contains_key(1, SETT));
+		});
+}
+
+#[test]
+fn governed_adjust_supply_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let rationale = H256::repeat_byte(1);
+
+			assert_noop!(
+				Stp258Standard::governed_adjust_supply(Some(ALICE).into(), SETT, 100 * 10_000, rationale),
+				BadOrigin
+			);
+			assert_ok!(Stp258Standard::governed_adjust_supply(
+				Origin::root(),
+				SETT,
+				100 * 10_000,
+				rationale
+			));
+			assert_eq!(Stp258Standard::free_balance(SETT, &TreasuryAccount::get()), 100 * 10_000);
+			assert_eq!(Stp258Standard::total_issuance(SETT), 500 * 10_000);
+			assert_eq!(
+				Stp258Standard::supply_history(SETT),
+				vec![SupplyChange {
+					block: 1,
+					amount: 100 * 10_000,
+					source: SupplyChangeSource::Governance(rationale),
+				}]
+			);
+
+			let adjusted_event = Event::stp258_standard(crate::Event::SupplyAdjusted(SETT, 100 * 10_000, rationale));
+			assert_event(adjusted_event);
+
+			for block in 2..5 {
+				System::set_block_number(block);
+				assert_ok!(Stp258Standard::governed_adjust_supply(
+					Origin::root(),
+					SETT,
+					-10 * 10_000,
+					rationale
+				));
+			}
+			let history = Stp258Standard::supply_history(SETT);
+			assert_eq!(history.len(), 3);
+			assert_eq!(history[0].block, 2);
+			assert_eq!(Stp258Standard::total_issuance(SETT), 470 * 10_000);
+		});
+}
+
+#[test]
+fn genesis_presets_should_work() {
+	assert!(crate::GenesisConfig::<Runtime>::preset("testnet").is_none());
+
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+	let mut config = crate::GenesisConfig::<Runtime>::preset("devnet").unwrap();
+	config.lock_prefixes = vec![*b"stk"];
+	GenesisBuild::<Runtime>::assimilate_storage(&config, &mut t).unwrap();
+
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(
+			Stp258Standard::serp_parameter(SerpParameterKey::AdjustmentFrequency),
+			Some(SerpParameter::AdjustmentFrequency(10))
+		);
+		assert_eq!(
+			Stp258Standard::serp_parameter(SerpParameterKey::SettPayRatio),
+			Some(SerpParameter::SettPayRatio(Perbill::from_percent(75)))
+		);
+		assert!(Stp258Standard::lock_prefix_registered(*b"stk"));
+	});
+}
+
+#[test]
+fn widen_balance_migration_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::<Runtime>::put(Releases::V2);
+		frame_support::storage::unhashed::put(&TotalLocked::<Runtime>::hashed_key_for(SETT), &5u32);
+		frame_support::storage::unhashed::put(
+			&Locks::<Runtime>::hashed_key_for(&ALICE, SETT),
+			&vec![BalanceLock { id: ID_1, amount: 5u32 }],
+		);
+		frame_support::storage::unhashed::put(
+			&SerpParameters::<Runtime>::hashed_key_for(SerpParameterKey::SerpQuoteMultiple),
+			&SerpParameter::<u64, u32>::SerpQuoteMultiple(3),
+		);
+
+		migrations::widen_balance::migrate::<Runtime, (), u32, i32>();
+		assert_eq!(Stp258Standard::storage_version(), Releases::V3);
+
+		assert_eq!(Stp258Standard::total_locked(SETT), 5);
+		assert_eq!(
+			Stp258Standard::locks(&ALICE, SETT),
+			vec![BalanceLock { id: ID_1, amount: 5 }]
+		);
+		assert_eq!(
+			Stp258Standard::serp_parameter(SerpParameterKey::SerpQuoteMultiple),
+			Some(SerpParameter::SerpQuoteMultiple(3))
+		);
+	});
+}
+
+#[test]
+fn widen_balance_migration_should_translate_later_items() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::<Runtime>::put(Releases::V2);
+		frame_support::storage::unhashed::put(
+			&Delegations::<Runtime>::hashed_key_for(&ALICE, (SETT, BOB)),
+			&Delegation {
+				max_per_period: 7u32,
+				era: 2,
+				spent: 3u32,
+			},
+		);
+		frame_support::storage::unhashed::put(&AccumulatedDust::<Runtime>::hashed_key_for(SETT), &4u32);
+		frame_support::storage::unhashed::put(&NextEraIssuance::<Runtime>::hashed_key(), &9u32);
+
+		migrations::widen_balance::migrate::<Runtime, (), u32, i32>();
+
+		assert_eq!(
+			Stp258Standard::delegation(&ALICE, (SETT, BOB)),
+			Some(Delegation {
+				max_per_period: 7,
+				era: 2,
+				spent: 3,
+			})
+		);
+		assert_eq!(Stp258Standard::accumulated_dust(SETT), 4);
+		assert_eq!(Stp258Standard::next_era_issuance(), 9);
+	});
+}
+
+#[test]
+fn widen_balance_migration_should_run_once() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_support::storage::unhashed::put(&AccumulatedDust::<Runtime>::hashed_key_for(SETT), &4u32);
+
+		// storage not yet at V2 is left alone
+		migrations::widen_balance::migrate::<Runtime, (), u32, i32>();
+		assert_eq!(Stp258Standard::storage_version(), Releases::V1);
+		assert_eq!(
+			frame_support::storage::unhashed::get::<u32>(&AccumulatedDust::<Runtime>::hashed_key_for(SETT)),
+			Some(4)
+		);
+
+		StorageVersion::<Runtime>::put(Releases::V2);
+		migrations::widen_balance::migrate::<Runtime, (), u32, i32>();
+		assert_eq!(Stp258Standard::accumulated_dust(SETT), 4);
+
+		// a second run does not decode the widened values as narrow ones
+		migrations::widen_balance::migrate::<Runtime, (), u32, i32>();
+		assert_eq!(Stp258Standard::storage_version(), Releases::V3);
+		assert_eq!(Stp258Standard::accumulated_dust(SETT), 4);
+	});
+}
+
+#[test]
+fn migrate_to_v2_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Stp258Standard::storage_version(), Releases::V1);
+		Locks::<Runtime>::insert(
+			&ALICE,
+			SETT,
+			vec![
+				BalanceLock { id: ID_1, amount: 5 },
+				BalanceLock {
+					id: lock_id::namespaced(*b"stk", 1),
+					amount: 8,
+				},
+			],
+		);
+		Locks::<Runtime>::insert(&BOB, SETT, vec![BalanceLock { id: ID_1, amount: 2 }]);
+
+		migrations::MigrateToV2::<Runtime>::on_runtime_upgrade();
+		assert_eq!(Stp258Standard::storage_version(), Releases::V2);
+		assert_eq!(Stp258Standard::total_locked(SETT), 10);
+
+		Locks::<Runtime>::remove(&BOB, SETT);
+		migrations::MigrateToV2::<Runtime>::on_runtime_upgrade();
+		assert_eq!(Stp258Standard::total_locked(SETT), 10);
+	});
+}
+
+#[test]
+fn endow_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		endow(&ALICE, DNAR, 10);
+		endow(&ALICE, SETT, 10 * 10_000);
+		assert_eq!(Stp258Standard::free_balance(DNAR, &ALICE), 10);
+		assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 10 * 10_000);
+	});
+}
+
+#[cfg(feature = "dev")]
+#[test]
+fn dev_mint_to_self_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(Stp258Dev::dev_mint_to_self(Origin::root(), SETT, 10 * 10_000), BadOrigin);
+		assert_ok!(Stp258Dev::dev_mint_to_self(Some(ALICE).into(), SETT, 10 * 10_000));
+		assert_ok!(Stp258Dev::dev_mint_to_self(Some(ALICE).into(), DNAR, 10));
+		assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 10 * 10_000);
+		assert_eq!(Stp258Standard::free_balance(DNAR, &ALICE), 10);
+	});
+}
+
+#[cfg(feature = "dev")]
+#[test]
+fn dev_set_price_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let price = FixedU128::saturating_from_rational(3, 2);
+		assert_noop!(Stp258Dev::dev_set_price(Origin::root(), SETT, Some(price)), BadOrigin);
+		assert_eq!(Stp258Dev::price(SETT), None);
+		assert_ok!(Stp258Dev::dev_set_price(Some(ALICE).into(), SETT, Some(price)));
+		assert_eq!(Stp258Dev::price(SETT), Some(price));
+		assert_ok!(Stp258Dev::dev_set_price(Some(BOB).into(), SETT, None));
+		assert_eq!(Stp258Dev::price(SETT), None);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn try_state_should_detect_drift() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &ALICE, 50 * 10_000));
+			assert_ok!(Stp258Standard::set_lock_until(ID_1, DNAR, &BOB, 50, 3));
+			assert_ok!(Stp258Standard::do_try_state());
+
+			// another pallet removes the lock from the backing currency
+			assert_ok!(<Stp258Serp as Stp258CurrencyLockable<AccountId>>::remove_lock(ID_1, SETT, &ALICE));
+			assert!(Stp258Standard::do_try_state().is_err());
+			assert_ok!(<Stp258Serp as Stp258CurrencyLockable<AccountId>>::set_lock(ID_1, SETT, &ALICE, 50 * 10_000));
+			assert_ok!(Stp258Standard::do_try_state());
+
+			TotalLocked::<Runtime>::insert(SETT, 1);
+			assert!(Stp258Standard::do_try_state().is_err());
+		});
+}
+
+#[test]
+fn benchmark_helpers_should_fill_worst_case_state() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(benchmark_helpers::set_max_locks::<Runtime, ()>(SETT, &ALICE, 10, 5));
+			assert_eq!(Stp258Standard::locks(&ALICE, SETT).len(), 5);
+			assert_eq!(Stp258Standard::total_locked(SETT), 10);
+
+			assert_ok!(benchmark_helpers::fill_lock_expiry_queue::<Runtime, ()>(DNAR, &BOB, 10, 3, 4));
+			assert_eq!(Stp258Standard::lock_expiry_queue(3).len(), 4);
+
+			assert_ok!(benchmark_helpers::fill_pending_updates::<Runtime, ()>(SETT, &ALICE, 10, 3));
+			assert_eq!(Stp258Standard::next_update_id(), 3);
+			assert_ok!(Stp258Standard::execute_update(Some(BOB).into(), 2));
+		});
+}
+
+proptest! {
+	#[test]
+	fn update_balance_round_trips((currency_id, balance, amount) in currency_balance_amount()) {
+		ExtBuilder::default()
+			.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+			.build()
+			.execute_with(|| {
+				let issuance = Stp258Standard::total_issuance(currency_id);
+				assert_ok!(<Stp258Standard as Stp258CurrencyExtended<AccountId>>::update_balance(
+					currency_id, &ALICE, amount
+				));
+				assert_ok!(<Stp258Standard as Stp258CurrencyExtended<AccountId>>::update_balance(
+					currency_id, &ALICE, -amount
+				));
+				assert_eq!(Stp258Standard::free_balance(currency_id, &ALICE), balance);
+				assert_eq!(Stp258Standard::total_issuance(currency_id), issuance);
+			});
+	}
+
+	#[test]
+	fn usable_balance_never_exceeds_free_balance(
+		(currency_id, balance) in endowed_currency(),
+		locked in 0..=200 * 10_000u64,
+	) {
+		ExtBuilder::default()
+			.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+			.build()
+			.execute_with(|| {
+				assert_ok!(Stp258Standard::set_lock(ID_1, currency_id, &ALICE, locked));
+				let usable = Stp258Standard::usable_balance(currency_id, &ALICE);
+				assert!(usable <= balance);
+				assert_eq!(usable, balance.saturating_sub(locked));
+			});
+	}
+}
+
+#[test]
+fn units_should_convert_by_base_unit() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(units::decimals::<Runtime, ()>(SETT), Some(4));
+		assert_eq!(units::decimals::<Runtime, ()>(JUSD), Some(3));
+		assert_eq!(units::whole_to_base_units::<Runtime, ()>(SETT, 12), Some(120_000));
+		assert_eq!(units::whole_to_base_units::<Runtime, ()>(SETT, u64::max_value()), None);
+		assert_eq!(units::to_whole_units::<Runtime, ()>(JUSD, 12_340), (12, 340));
+
+		assert_eq!(units::to_base_units::<Runtime, ()>(SETT, "12.34"), Some(123_400));
+		assert_eq!(units::to_base_units::<Runtime, ()>(JUSD, "12"), Some(12_000));
+		assert_eq!(units::to_base_units::<Runtime, ()>(JUSD, "0.0001"), None);
+		assert_eq!(units::to_base_units::<Runtime, ()>(JUSD, "-1"), None);
+		assert_eq!(units::to_base_units::<Runtime, ()>(JUSD, ".5"), None);
+		assert_eq!(units::from_base_units::<Runtime, ()>(SETT, 123_400), Some("12.34".into()));
+		assert_eq!(units::from_base_units::<Runtime, ()>(JUSD, 12_000), Some("12".into()));
+	});
+}
+
+#[test]
+fn ss58_should_render_protocol_accounts() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			ss58::protocol_accounts::<Runtime, ()>(),
+			vec![
+				("treasury".to_string(), TreasuryAccount::get()),
+				("serper".to_string(), SERPER),
+				("dust/2".to_string(), Stp258Standard::dust_account(SETT)),
+				("dust/3".to_string(), Stp258Standard::dust_account(JUSD)),
+			]
+		);
+
+		let pot = Stp258Standard::register_sub_account(SubAccount::SerpPot).unwrap();
+		assert_ok!(Stp258Standard::derive_deposit_account(Some(ALICE).into(), 7, false));
+		let accounts = ss58::protocol_accounts::<Runtime, ()>();
+		assert!(accounts.contains(&("sub_account/SerpPot".to_string(), pot)));
+		assert!(accounts.contains(&(
+			format!("deposit/{:?}/7", ALICE),
+			Stp258Standard::deposit_account_of(&ALICE, 7)
+		)));
+		assert_eq!(
+			ss58::to_ss58(&sp_runtime::AccountId32::new([0u8; 32]), 42),
+			"5C4hrfjw9DjXZTzV3MwzrrAr9P1MJhSrvWGWqi1eSuyUpnhM"
+		);
+	});
+}
+
+#[test]
+fn transfer_index_keys_should_be_distinct() {
+	let key = Stp258Standard::transfer_index_key(&ALICE, 1, 0);
+	assert!(key.starts_with(&TRANSFER_INDEX_PREFIX.encode()));
+	assert_ne!(key, Stp258Standard::transfer_index_key(&BOB, 1, 0));
+	assert_ne!(key, Stp258Standard::transfer_index_key(&ALICE, 2, 0));
+	assert_ne!(key, Stp258Standard::transfer_index_key(&ALICE, 1, 1));
+
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 50));
+			assert_transferred(SETT, &ALICE, &BOB, 50);
+		});
+}
+
+#[test]
+fn next_adjustment_block_should_follow_frequency() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(Stp258Standard::next_adjustment_block(SETT), None);
+
+		assert_ok!(Stp258Standard::set_serp_parameter(
+			Origin::root(),
+			SerpParameter::AdjustmentFrequency(10)
+		));
+		assert_eq!(Stp258Standard::next_adjustment_block(SETT), Some(10));
+		assert_eq!(Stp258Standard::next_adjustment_block(DNAR), None);
+
+		System::set_block_number(10);
+		assert_eq!(Stp258Standard::next_adjustment_block(SETT), Some(20));
+	});
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_should_be_reported() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 50));
+			assert_ok!(Stp258Standard::governed_adjust_supply(
+				Origin::root(),
+				JUSD,
+				100,
+				H256::repeat_byte(1)
+			));
+			assert!(Stp258Standard::withdraw(SETT, &ALICE, 1_000 * 10_000).is_err());
+			assert_eq!(
+				recorded_metrics(),
+				vec![("transfer", SETT), ("serp_action", JUSD), ("failed_withdrawal", SETT)]
+			);
+		});
+}
+
+#[test]
+fn trace_events_should_follow_feature() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			endow(&ALICE, DNAR, 10);
+			let traced = Event::stp258_standard(crate::Event::Traced(DNAR, Trace::RoutedNative));
+			assert_eq!(
+				System::events().iter().any(|record| record.event == traced),
+				cfg!(feature = "trace-events")
+			);
+		});
+}
+
+#[test]
+fn export_state_should_dump_storage() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &ALICE, 50));
+			assert_ok!(Stp258Standard::set_serp_parameter(
+				Origin::root(),
+				SerpParameter::AdjustmentFrequency(10)
+			));
+
+			let state = state_dump::export_state::<Runtime, ()>();
+			assert_eq!(state["locks"].as_array().map(|locks| locks.len()), Some(1));
+			assert_eq!(state["total_locked"][0][1], 50);
+			assert_eq!(state["serp_parameters"].as_array().map(|p| p.len()), Some(1));
+			assert!(state["savings"].as_array().map_or(false, |savings| savings.is_empty()));
+		});
+}
+
+#[test]
+fn currency_snapshot_should_restore_parameters() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+			assert_ok!(Stp258Standard::set_volume_tracking(Origin::root(), SETT, true));
+
+			assert_noop!(
+				Stp258Standard::snapshot_currency(Some(ALICE).into(), SETT),
+				BadOrigin
+			);
+			assert_ok!(Stp258Standard::snapshot_currency(Origin::root(), SETT));
+			let snapshot_id = match last_stp258_event() {
+				Some(crate::Event::CurrencySnapshotTaken(SETT, snapshot_id)) => snapshot_id,
+				event => panic!("unexpected event {:?}", event),
+			};
+			let snapshot = Stp258Standard::currency_snapshot(snapshot_id).unwrap();
+			assert_eq!(snapshot.total_issuance, Stp258Standard::total_issuance(SETT));
+			assert_eq!(snapshot.demurrage_rate, Some(Perbill::from_percent(1)));
+
+			assert_ok!(Stp258Standard::set_demurrage_rate(Origin::root(), SETT, None));
+			assert_ok!(Stp258Standard::set_volume_tracking(Origin::root(), SETT, false));
+			assert_ok!(Stp258Standard::set_savings_rate(
+				Origin::root(),
+				SETT,
+				Perbill::from_percent(2)
+			));
+
+			assert_noop!(
+				Stp258Standard::restore_currency(Origin::root(), H256::repeat_byte(9)),
+				Error::<Runtime>::UnknownSnapshot
+			);
+			assert_ok!(Stp258Standard::restore_currency(Origin::root(), snapshot_id));
+			assert_event(Event::stp258_standard(crate::Event::CurrencyRestored(SETT, snapshot_id)));
+			assert_eq!(Stp258Standard::demurrage_rate(SETT), Some(Perbill::from_percent(1)));
+			assert!(Stp258Standard::volume_tracked(SETT));
+			assert_eq!(Stp258Standard::savings_rate(SETT), Perbill::zero());
+		});
+}
+
+#[test]
+fn holds_should_only_release_their_reason() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::reserve(SETT, &ALICE, 10));
+			assert_ok!(Stp258Standard::hold(HoldReason::Escrow, SETT, &ALICE, 30));
+			assert_ok!(Stp258Standard::hold(HoldReason::Bond, SETT, &ALICE, 20));
+			assert_event(Event::stp258_standard(crate::Event::Held(SETT, ALICE, HoldReason::Bond, 20)));
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 60);
+			assert_eq!(Stp258Standard::total_balance_on_hold(SETT, &ALICE), 50);
+
+			// only the unheld reserve is released
+			assert_eq!(Stp258Standard::unreserve(SETT, &ALICE, 40), 30);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 50);
+
+			assert_eq!(Stp258Standard::release(HoldReason::Escrow, SETT, &ALICE, 100), 30);
+			assert_event(Event::stp258_standard(crate::Event::Released(SETT, ALICE, HoldReason::Escrow, 30)));
+			assert_eq!(Stp258Standard::balance_on_hold(HoldReason::Escrow, SETT, &ALICE), 0);
+			assert_eq!(Stp258Standard::balance_on_hold(HoldReason::Bond, SETT, &ALICE), 20);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 20);
+
+			assert_eq!(Stp258Standard::release(HoldReason::Bond, SETT, &ALICE, 20), 20);
+			assert!(!Holds::<Runtime>::contains_key(ALICE, SETT));
+			assert_eq!(Stp258Standard::total_reserved(SETT), 0);
+		});
+}
+
+#[test]
+fn unreserve_to_should_move_unheld_reserve() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::reserve(SETT, &ALICE, 40));
+			assert_ok!(Stp258Standard::hold(HoldReason::Escrow, SETT, &ALICE, 10));
+			let bob_free = Stp258Standard::free_balance(SETT, &BOB);
+
+			assert_eq!(Stp258Standard::unreserve_to(SETT, &ALICE, &BOB, 100), Ok(40));
+			assert_event(Event::stp258_standard(crate::Event::UnreservedTo(SETT, ALICE, BOB, 40)));
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 10);
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), bob_free + 40);
+			assert_eq!(Stp258Standard::unreserve_to(SETT, &ALICE, &BOB, 10), Ok(0));
+		});
+}
+
+#[test]
+fn repatriate_reserved_should_not_move_held_balance() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::reserve(SETT, &ALICE, 40));
+			assert_ok!(Stp258Standard::hold(HoldReason::Escrow, SETT, &ALICE, 30));
+			let bob_free = Stp258Standard::free_balance(SETT, &BOB);
+
+			assert_eq!(
+				Stp258Standard::repatriate_reserved(SETT, &ALICE, &BOB, 100, BalanceStatus::Free),
+				Ok(60)
+			);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 30);
+			assert_eq!(Stp258Standard::balance_on_hold(HoldReason::Escrow, SETT, &ALICE), 30);
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), bob_free + 40);
+		});
+}
+
+#[test]
+fn repatriate_reserved_multi_should_not_move_held_balance() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::reserve(SETT, &ALICE, 40));
+			assert_ok!(Stp258Standard::hold(HoldReason::Bond, SETT, &ALICE, 30));
+
+			assert_noop!(
+				Stp258Standard::repatriate_reserved_multi(
+					SETT,
+					&ALICE,
+					vec![(BOB, 40, BalanceStatus::Free), (SERPER, 20, BalanceStatus::Reserved)]
+				),
+				Error::<Runtime>::InsufficientReservedBalance
+			);
+
+			assert_ok!(Stp258Standard::repatriate_reserved_multi(
+				SETT,
+				&ALICE,
+				vec![(BOB, 30, BalanceStatus::Free), (SERPER, 10, BalanceStatus::Reserved)]
+			));
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 30);
+			assert_eq!(Stp258Standard::balance_on_hold(HoldReason::Bond, SETT, &ALICE), 30);
+		});
+}
+
+#[test]
+fn slash_reserved_should_not_slash_held_balance() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::reserve(SETT, &ALICE, 40));
+			assert_ok!(Stp258Standard::hold(HoldReason::Escrow, SETT, &ALICE, 30));
+			let issuance = Stp258Standard::total_issuance(SETT);
+
+			assert_eq!(Stp258Standard::slash_reserved(SETT, &ALICE, 100), 60);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 30);
+			assert_eq!(Stp258Standard::balance_on_hold(HoldReason::Escrow, SETT, &ALICE), 30);
+			assert_eq!(Stp258Standard::total_issuance(SETT), issuance - 40);
+			assert_eq!(Stp258Standard::total_reserved(SETT), 30);
+		});
+}
+
+#[test]
+fn slash_should_not_slash_held_balance() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			for (who, order) in vec![
+				(ALICE, SlashOrder::FreeFirst),
+				(BOB, SlashOrder::ReservedFirst),
+				(SERPER, SlashOrder::Proportional),
+			] {
+				assert_ok!(Stp258Standard::set_slash_order(Origin::root(), SETT, order));
+				assert_ok!(Stp258Standard::reserve(SETT, &who, 40));
+				assert_ok!(Stp258Standard::hold(HoldReason::Escrow, SETT, &who, 30));
+
+				assert_eq!(Stp258Standard::slash(SETT, &who, 100 * 10_000), 30);
+				assert_eq!(Stp258Standard::free_balance(SETT, &who), 0);
+				assert_eq!(Stp258Standard::reserved_balance(SETT, &who), 30);
+				assert_eq!(Stp258Standard::balance_on_hold(HoldReason::Escrow, SETT, &who), 30);
+			}
+			assert_eq!(Stp258Standard::total_reserved(SETT), 3 * 30);
+		});
+}
+
+#[test]
+fn freezes_should_overlap_apart_from_locks() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let vote: LockIdentifier = *b"vote    ";
+			let free = Stp258Standard::free_balance(SETT, &ALICE);
+
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &ALICE, 40));
+			assert_ok!(Stp258Standard::set_freeze(vote, SETT, &ALICE, 30));
+			assert_ok!(Stp258Standard::set_freeze(ID_1, SETT, &ALICE, 50));
+			assert_event(Event::stp258_standard(crate::Event::Frozen(SETT, ALICE, ID_1, 50)));
+			assert_eq!(Stp258Standard::balance_frozen(SETT, &ALICE), 50);
+			assert_eq!(Stp258Standard::usable_balance(SETT, &ALICE), free - 50);
+			// the lock with the same id is untouched
+			assert_eq!(Stp258Standard::total_locked(SETT), 40);
+
+			assert_ok!(Stp258Standard::thaw(ID_1, SETT, &ALICE));
+			assert_event(Event::stp258_standard(crate::Event::Thawed(SETT, ALICE, ID_1)));
+			assert_eq!(Stp258Standard::balance_frozen(SETT, &ALICE), 30);
+			assert_eq!(Stp258Standard::usable_balance(SETT, &ALICE), free - 40);
+
+			assert_ok!(Stp258Standard::thaw(vote, SETT, &ALICE));
+			assert!(!Freezes::<Runtime>::contains_key(ALICE, SETT));
+		});
+}
+
+#[test]
+fn slash_should_follow_slash_order() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::reserve(JUSD, &ALICE, 60_000));
+
+			assert_noop!(
+				Stp258Standard::set_slash_order(Some(ALICE).into(), JUSD, SlashOrder::ReservedFirst),
+				BadOrigin
+			);
+			assert_ok!(Stp258Standard::set_slash_order(Origin::root(), JUSD, SlashOrder::ReservedFirst));
+			assert_event(Event::stp258_standard(crate::Event::SlashOrderSet(JUSD, SlashOrder::ReservedFirst)));
+			assert_eq!(Stp258Standard::slash(JUSD, &ALICE, 20_000), 0);
+			assert_eq!(Stp258Standard::reserved_balance(JUSD, &ALICE), 40_000);
+			assert_eq!(Stp258Standard::free_balance(JUSD, &ALICE), 40_000);
+			assert_event(Event::stp258_standard(crate::Event::Slashed(
+				JUSD,
+				ALICE,
+				20_000,
+				SlashDestination::Burn,
+				Some(SlashOrder::ReservedFirst),
+				1,
+			)));
+
+			assert_ok!(Stp258Standard::set_slash_order(Origin::root(), JUSD, SlashOrder::Proportional));
+			assert_eq!(Stp258Standard::slash(JUSD, &ALICE, 20_000), 0);
+			assert_eq!(Stp258Standard::reserved_balance(JUSD, &ALICE), 30_000);
+			assert_eq!(Stp258Standard::free_balance(JUSD, &ALICE), 30_000);
+			assert_eq!(Stp258Standard::total_reserved(JUSD), 30_000);
+
+			assert_ok!(Stp258Standard::set_slash_order(Origin::root(), JUSD, SlashOrder::FreeFirst));
+			assert!(!SlashOrders::<Runtime>::contains_key(JUSD));
+		});
+}
+
+#[test]
+fn repatriate_reserved_multi_should_be_atomic() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::reserve(SETT, &ALICE, 100));
+			let bob_free = Stp258Standard::free_balance(SETT, &BOB);
+			let serper_reserved = Stp258Standard::reserved_balance(SETT, &SERPER);
+
+			assert_noop!(
+				Stp258Standard::repatriate_reserved_multi(
+					SETT,
+					&ALICE,
+					vec![(BOB, 60, BalanceStatus::Free), (SERPER, 60, BalanceStatus::Reserved)]
+				),
+				Error::<Runtime>::InsufficientReservedBalance
+			);
+
+			assert_ok!(Stp258Standard::repatriate_reserved_multi(
+				SETT,
+				&ALICE,
+				vec![(BOB, 60, BalanceStatus::Free), (SERPER, 40, BalanceStatus::Reserved)]
+			));
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &ALICE), 0);
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), bob_free + 60);
+			assert_eq!(Stp258Standard::reserved_balance(SETT, &SERPER), serper_reserved + 40);
+		});
+}
+
+#[test]
+fn failed_slash_credit_should_be_logged() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			record_logs();
+			let charlie = AccountId::new([5u8; 32]);
+			assert_ok!(Stp258Standard::set_slash_destination(
+				Origin::root(),
+				DNAR,
+				SlashDestination::Account(charlie.clone())
+			));
+			ExistentialDeposit::set(50);
+			let issuance = Stp258Standard::total_issuance(DNAR);
+
+			// the credit cannot create Charlie, so the slash stands and is burned
+			assert_eq!(Stp258Standard::slash(DNAR, &ALICE, 10), 0);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &ALICE), 90);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &charlie), 0);
+			assert_eq!(Stp258Standard::total_issuance(DNAR), issuance - 10);
+			assert!(recorded_logs()
+				.iter()
+				.any(|(level, message)| *level == log::Level::Warn && message.contains("burned")));
+		});
+}
+
+#[test]
+fn release_fallback_should_avoid_dusting() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let treasury = TreasuryAccount::get();
+			let charlie = AccountId::new([5u8; 32]);
+			assert_ok!(Stp258Standard::reserve(DNAR, &ALICE, 30));
+			endow(&treasury, DNAR, 20);
+			ExistentialDeposit::set(10);
+
+			GetReleaseFallback::set(ReleaseFallback::KeepReserved);
+			assert_eq!(
+				Stp258Standard::repatriate_reserved(DNAR, &ALICE, &charlie, 5, BalanceStatus::Free),
+				Ok(5)
+			);
+			assert_event(Event::stp258_standard(crate::Event::ReleaseFallbackApplied(
+				DNAR,
+				charlie.clone(),
+				5,
+				ReleaseFallback::KeepReserved,
+			)));
+			assert_eq!(Stp258Standard::reserved_balance(DNAR, &ALICE), 30);
+
+			GetReleaseFallback::set(ReleaseFallback::Treasury);
+			assert_eq!(
+				Stp258Standard::repatriate_reserved(DNAR, &ALICE, &charlie, 5, BalanceStatus::Free),
+				Ok(0)
+			);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &treasury), 25);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &charlie), 0);
+
+			GetReleaseFallback::set(ReleaseFallback::TopUp);
+			assert_eq!(
+				Stp258Standard::repatriate_reserved(DNAR, &ALICE, &charlie, 5, BalanceStatus::Free),
+				Ok(0)
+			);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &charlie), 10);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &treasury), 20);
+			assert_eq!(Stp258Standard::reserved_balance(DNAR, &ALICE), 20);
+			assert_event(Event::stp258_standard(crate::Event::ReleaseFallbackApplied(
+				DNAR,
+				charlie,
+				5,
+				ReleaseFallback::TopUp,
+			)));
+		});
+}
+
+#[test]
+fn release_fallback_should_avoid_dusting_non_native() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let treasury = TreasuryAccount::get();
+			let charlie = AccountId::new([5u8; 32]);
+			assert_ok!(Stp258Standard::reserve(JUSD, &ALICE, 30));
+			set_existential_deposit(JUSD, 10);
+
+			// the treasury cannot top Charlie up, so nothing is moved
+			GetReleaseFallback::set(ReleaseFallback::TopUp);
+			assert!(Stp258Standard::repatriate_reserved(JUSD, &ALICE, &charlie, 5, BalanceStatus::Free).is_err());
+			assert_eq!(Stp258Standard::reserved_balance(JUSD, &ALICE), 30);
+			assert_eq!(Stp258Standard::total_balance(JUSD, &treasury), 0);
+			assert_eq!(Stp258Standard::total_balance(JUSD, &charlie), 0);
+			assert!(!System::events().iter().any(|record| matches!(
+				record.event,
+				Event::stp258_standard(crate::Event::ReleaseFallbackApplied(..))
+			)));
+
+			endow(&treasury, JUSD, 20);
+			GetReleaseFallback::set(ReleaseFallback::Treasury);
+			assert_eq!(
+				Stp258Standard::repatriate_reserved(JUSD, &ALICE, &charlie, 5, BalanceStatus::Free),
+				Ok(0)
+			);
+			assert_eq!(Stp258Standard::free_balance(JUSD, &treasury), 25);
+			assert_eq!(Stp258Standard::free_balance(JUSD, &charlie), 0);
+			assert_event(Event::stp258_standard(crate::Event::ReleaseFallbackApplied(
+				JUSD,
+				charlie.clone(),
+				5,
+				ReleaseFallback::Treasury,
+			)));
+
+			GetReleaseFallback::set(ReleaseFallback::TopUp);
+			assert_eq!(
+				Stp258Standard::repatriate_reserved(JUSD, &ALICE, &charlie, 5, BalanceStatus::Reserved),
+				Ok(0)
+			);
+			assert_eq!(Stp258Standard::free_balance(JUSD, &charlie), 5);
+			assert_eq!(Stp258Standard::reserved_balance(JUSD, &charlie), 5);
+			assert_eq!(Stp258Standard::free_balance(JUSD, &treasury), 20);
+			assert_eq!(Stp258Standard::reserved_balance(JUSD, &ALICE), 20);
+			assert_event(Event::stp258_standard(crate::Event::ReleaseFallbackApplied(
+				JUSD,
+				charlie,
+				5,
+				ReleaseFallback::TopUp,
+			)));
+		});
+}
+
+#[test]
+fn delegated_transfer_should_respect_budget() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				Stp258Standard::delegated_transfer(Some(BOB).into(), ALICE, SERPER, SETT, 10),
+				Error::<Runtime>::NotDelegate
+			);
+
+			assert_ok!(Stp258Standard::add_delegate(Some(ALICE).into(), SETT, BOB, 100));
+			assert_event(Event::stp258_standard(crate::Event::DelegateAdded(SETT, ALICE, BOB, 100)));
+			assert_ok!(Stp258Standard::delegated_transfer(Some(BOB).into(), ALICE, SERPER, SETT, 60));
+			assert_transferred(SETT, &ALICE, &SERPER, 60);
+			assert_noop!(
+				Stp258Standard::delegated_transfer(Some(BOB).into(), ALICE, SERPER, SETT, 50),
+				Error::<Runtime>::DelegationBudgetExceeded
+			);
+			assert_noop!(
+				Stp258Standard::delegated_transfer(Some(BOB).into(), ALICE, SERPER, JUSD, 10),
+				Error::<Runtime>::NotDelegate
+			);
+
+			// the budget renews every era
+			System::set_block_number(ERA_LENGTH);
+			assert_ok!(Stp258Standard::delegated_transfer(Some(BOB).into(), ALICE, SERPER, SETT, 100));
+
+			assert_ok!(Stp258Standard::remove_delegate(Some(ALICE).into(), SETT, BOB));
+			assert_event(Event::stp258_standard(crate::Event::DelegateRemoved(SETT, ALICE, BOB)));
+			assert_noop!(
+				Stp258Standard::delegated_transfer(Some(BOB).into(), ALICE, SERPER, SETT, 1),
+				Error::<Runtime>::NotDelegate
+			);
+		});
+}
+
+#[test]
+fn calls_should_take_multi_address_destinations() {
+	use mock::indexed::{new_test_ext, Origin, Stp258Standard};
+	use sp_runtime::MultiAddress;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), MultiAddress::Index(1), SETT, 10 * 10_000));
+		assert_ok!(Stp258Standard::transfer(
+			Some(ALICE).into(),
+			MultiAddress::Raw(BOB.encode()),
+			SETT,
+			10 * 10_000
+		));
+		assert_ok!(Stp258Standard::transfer_native_currency(
+			Some(ALICE).into(),
+			MultiAddress::Address32([1u8; 32]),
+			50
+		));
+		assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 120 * 10_000);
+		assert_eq!(Stp258Standard::free_balance(DNAR, &BOB), 150);
+
+		assert_ok!(Stp258Standard::update_balance(
+			Origin::root(),
+			MultiAddress::Index(0),
+			SETT,
+			5 * 10_000
+		));
+		assert_ok!(Stp258Standard::add_delegate(
+			Some(ALICE).into(),
+			SETT,
+			MultiAddress::Index(1),
+			20 * 10_000
+		));
+		assert_ok!(Stp258Standard::delegated_transfer(
+			Some(BOB).into(),
+			MultiAddress::Index(0),
+			MultiAddress::Index(1),
+			SETT,
+			10 * 10_000
+		));
+		assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 75 * 10_000);
+		assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 130 * 10_000);
+
+		assert_noop!(
+			Stp258Standard::transfer(Some(ALICE).into(), MultiAddress::Index(2), SETT, 10),
+			DispatchError::CannotLookup
+		);
+		assert_noop!(
+			Stp258Standard::transfer(Some(ALICE).into(), MultiAddress::Address20([1u8; 20]), SETT, 10),
+			DispatchError::CannotLookup
+		);
+	});
+}
+
+#[test]
+fn guarded_transfer_should_wait_for_guardian() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_transfer_guard(Some(ALICE).into(), SETT, SERPER, 50));
+			assert_event(Event::stp258_standard(crate::Event::TransferGuardSet(SETT, ALICE, SERPER, 50)));
+			assert_noop!(
+				Stp258Standard::set_transfer_guard(Some(ALICE).into(), SETT, BOB, 10),
+				Error::<Runtime>::TransferGuardExists
+			);
+
+			// at or below the threshold transfers go straight through
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 50));
+			assert_transferred(SETT, &ALICE, &BOB, 50);
+
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 60));
+			assert_event(Event::stp258_standard(crate::Event::TransferAwaitingConfirmation(
+				0, SETT, ALICE, BOB, 60,
+			)));
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 100 * 10_000 + 50);
+			assert_noop!(
+				Stp258Standard::confirm_transfer(Some(BOB).into(), 0),
+				Error::<Runtime>::NotGuardian
+			);
+			assert_ok!(Stp258Standard::confirm_transfer(Some(SERPER).into(), 0));
+			assert_event(Event::stp258_standard(crate::Event::TransferConfirmed(0)));
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 100 * 10_000 + 110);
+			assert_noop!(
+				Stp258Standard::confirm_transfer(Some(SERPER).into(), 0),
+				Error::<Runtime>::UnknownPendingTransfer
+			);
+
+			// unconfirmed transfers lapse once the window has passed
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 60));
+			System::set_block_number(1 + TransferConfirmationWindow::get() + 1);
+			assert_ok!(Stp258Standard::confirm_transfer(Some(SERPER).into(), 1));
+			assert_event(Event::stp258_standard(crate::Event::TransferLapsed(1)));
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 100 * 10_000 + 110);
+			assert_eq!(Stp258Standard::pending_transfer(1), None);
+
+			assert_noop!(
+				Stp258Standard::remove_transfer_guard(Some(BOB).into(), ALICE, SETT),
+				Error::<Runtime>::NotGuardian
+			);
+			assert_ok!(Stp258Standard::remove_transfer_guard(Some(SERPER).into(), ALICE, SETT));
+			assert_event(Event::stp258_standard(crate::Event::TransferGuardRemoved(SETT, ALICE)));
+		});
+}
+
+#[test]
+fn transfer_with_authorization_should_work_once() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let pair = sr25519::Pair::from_seed(&[7u8; 32]);
+			let payer: AccountId = MultiSigner::from(pair.public()).into_account();
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(SETT, &ALICE, &payer, 100));
+
+			let authorization = TransferAuthorization {
+				from: payer.clone(),
+				dest: BOB,
+				currency_id: SETT,
+				amount: 40,
+				nonce: 0,
+				deadline: 5,
+			};
+			let signature = MultiSignature::from(pair.sign(&Stp258Standard::authorization_payload(&authorization)));
+			// signed for another chain
+			let other_chain = authorization.signing_payload(&H256::repeat_byte(1), &Stp258ModuleId::get());
+			assert_noop!(
+				Stp258Standard::transfer_with_authorization(
+					Some(SERPER).into(),
+					authorization.clone(),
+					MultiSignature::from(pair.sign(&other_chain))
+				),
+				Error::<Runtime>::InvalidAuthorizationSignature
+			);
+			let forged = TransferAuthorization {
+				amount: 100,
+				..authorization.clone()
+			};
+			assert_noop!(
+				Stp258Standard::transfer_with_authorization(Some(SERPER).into(), forged, signature.clone()),
+				Error::<Runtime>::InvalidAuthorizationSignature
+			);
+
+			assert_ok!(Stp258Standard::transfer_with_authorization(
+				Some(SERPER).into(),
+				authorization.clone(),
+				signature.clone()
+			));
+			assert_transferred(SETT, &payer, &BOB, 40);
+			assert_event(Event::stp258_standard(crate::Event::AuthorizationUsed(payer.clone(), 0)));
+			assert_eq!(Stp258Standard::authorization_nonce(&payer), 1);
+			assert_noop!(
+				Stp258Standard::transfer_with_authorization(Some(SERPER).into(), authorization, signature),
+				Error::<Runtime>::InvalidAuthorizationNonce
+			);
+
+			let late = TransferAuthorization {
+				from: payer.clone(),
+				dest: BOB,
+				currency_id: SETT,
+				amount: 40,
+				nonce: 1,
+				deadline: 5,
+			};
+			let signature = MultiSignature::from(pair.sign(&Stp258Standard::authorization_payload(&late)));
+			System::set_block_number(6);
+			assert_noop!(
+				Stp258Standard::transfer_with_authorization(Some(SERPER).into(), late, signature),
+				Error::<Runtime>::AuthorizationExpired
+			);
+		});
+}
+
+#[test]
+fn erc20_compat_should_answer_from_pallet_state() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_eq!(erc20_compat::name::<Runtime, ()>(JUSD), None);
+			assert_noop!(
+				Stp258Standard::set_currency_metadata(Origin::root(), JUSD, vec![b'x'; 33], b"JUSD".to_vec()),
+				Error::<Runtime>::MetadataTooLong
+			);
+			assert_ok!(Stp258Standard::set_currency_metadata(
+				Origin::root(),
+				JUSD,
+				b"Setheum Dollar".to_vec(),
+				b"JUSD".to_vec()
+			));
+			assert_event(Event::stp258_standard(crate::Event::CurrencyMetadataSet(JUSD)));
+			assert_eq!(erc20_compat::name::<Runtime, ()>(JUSD), Some(b"Setheum Dollar".to_vec()));
+			assert_eq!(erc20_compat::symbol::<Runtime, ()>(JUSD), Some(b"JUSD".to_vec()));
+			assert_eq!(erc20_compat::decimals::<Runtime, ()>(JUSD), Some(3));
+			assert_eq!(erc20_compat::total_supply::<Runtime, ()>(JUSD), 4 * 100 * 1_000);
+			assert_eq!(erc20_compat::balance_of::<Runtime, ()>(JUSD, &ALICE), 100 * 1_000);
+
+			assert_eq!(erc20_compat::allowance::<Runtime, ()>(JUSD, &ALICE, &BOB), 0);
+			assert_ok!(Stp258Standard::add_delegate(Some(ALICE).into(), JUSD, BOB, 100));
+			assert_ok!(Stp258Standard::delegated_transfer(Some(BOB).into(), ALICE, SERPER, JUSD, 30));
+			assert_eq!(erc20_compat::allowance::<Runtime, ()>(JUSD, &ALICE, &BOB), 70);
+			System::set_block_number(ERA_LENGTH);
+			assert_eq!(erc20_compat::allowance::<Runtime, ()>(JUSD, &ALICE, &BOB), 100);
+		});
+}
+
+#[test]
+fn balance_at_should_read_checkpoints() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(2);
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 2), None);
+			assert_ok!(Stp258Standard::enable_checkpointing(Origin::root(), SETT));
+			assert_event(Event::stp258_standard(crate::Event::CheckpointingEnabled(SETT)));
+			assert_noop!(
+				Stp258Standard::enable_checkpointing(Origin::root(), SETT),
+				Error::<Runtime>::CheckpointingAlreadyEnabled
+			);
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 2), Some(100 * 10_000));
+
+			System::set_block_number(4);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(SETT, &ALICE, &BOB, 10));
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(SETT, &ALICE, &BOB, 10));
+			System::set_block_number(7);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(SETT, &ALICE, 30));
+			assert_eq!(
+				Stp258Standard::balance_checkpoints(&ALICE, SETT),
+				vec![(2, 100 * 10_000), (4, 100 * 10_000 - 20), (7, 100 * 10_000 - 50)]
+			);
+
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 1), None);
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 3), Some(100 * 10_000));
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 5), Some(100 * 10_000 - 20));
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 9), Some(100 * 10_000 - 50));
+			assert_eq!(Stp258Standard::balance_at(SETT, &BOB, 3), Some(100 * 10_000));
+			assert_eq!(Stp258Standard::balance_at(SETT, &BOB, 4), Some(100 * 10_000 + 20));
+			assert_eq!(Stp258Standard::balance_at(SETT, &SERPER, 9), Some(100 * 10_000));
+
+			// the oldest checkpoints are dropped past `MaxCheckpoints`
+			System::set_block_number(8);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::deposit(SETT, &ALICE, 50));
+			System::set_block_number(9);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::deposit(SETT, &ALICE, 50));
+			assert_eq!(Stp258Standard::balance_checkpoints(&ALICE, SETT).len(), 4);
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 3), None);
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 9), Some(100 * 10_000 + 50));
+		});
+}
+
+#[test]
+fn child_trie_checkpoints_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(2);
+			assert_noop!(
+				Stp258Standard::prune_checkpoints(Origin::root(), SETT, 10),
+				Error::<Runtime>::CheckpointsNotInChildTrie
+			);
+			assert_ok!(Stp258Standard::enable_child_trie_checkpointing(Origin::root(), SETT));
+			assert_event(Event::stp258_standard(crate::Event::CheckpointingEnabled(SETT)));
+			assert_noop!(
+				Stp258Standard::enable_checkpointing(Origin::root(), SETT),
+				Error::<Runtime>::CheckpointingAlreadyEnabled
+			);
+
+			System::set_block_number(4);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(SETT, &ALICE, &BOB, 10));
+			assert_eq!(
+				Stp258Standard::checkpoints_of(SETT, &ALICE),
+				vec![(2, 100 * 10_000), (4, 100 * 10_000 - 10)]
+			);
+			assert_eq!(Stp258Standard::balance_checkpoints(&ALICE, SETT), vec![]);
+			let child_info = Stp258Standard::checkpoints_child_info(SETT);
+			assert!(child::get_raw(&child_info, &ALICE.encode()).is_some());
+			assert_ne!(child_info, Stp258Standard::checkpoints_child_info(JUSD));
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 3), Some(100 * 10_000));
+			assert_eq!(Stp258Standard::balance_at(SETT, &BOB, 4), Some(100 * 10_000 + 10));
+
+			System::set_block_number(6);
+			assert_ok!(Stp258Standard::prune_checkpoints(Origin::root(), SETT, 10));
+			assert_event(Event::stp258_standard(crate::Event::CheckpointsPruned(SETT, true)));
+			assert!(child::get_raw(&child_info, &ALICE.encode()).is_none());
+			assert!(child::get_raw(&child_info, &BOB.encode()).is_none());
+			assert!(!Stp258Standard::child_trie_checkpointing(SETT));
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 6), None);
+
+			// balances are no longer checkpointed, and can be again
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(SETT, &ALICE, &BOB, 10));
+			assert!(child::get_raw(&child_info, &ALICE.encode()).is_none());
+			assert_ok!(Stp258Standard::enable_checkpointing(Origin::root(), SETT));
+			assert_eq!(Stp258Standard::balance_at(SETT, &ALICE, 6), Some(100 * 10_000 - 20));
+		});
+}
+
+#[test]
+fn issuance_should_be_sampled_at_eras_and_adjustments() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Standard::set_serp_parameter(
+				Origin::root(),
+				SerpParameter::AdjustmentFrequency(4)
+			));
+			Stp258Standard::on_initialize(4);
+			assert_eq!(Stp258Standard::issuance_history(SETT), vec![(4, 400 * 10_000)]);
+			assert_eq!(Stp258Standard::issuance_history(DNAR), vec![]);
+
+			Stp258Standard::on_initialize(5);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(SETT, &ALICE, 10_000));
+			Stp258Standard::on_initialize(8);
+			Stp258Standard::on_initialize(ERA_LENGTH);
+			assert_eq!(Stp258Standard::issuance_history(DNAR), vec![(ERA_LENGTH, 400)]);
+			assert_eq!(
+				Stp258Standard::issuance_history(SETT),
+				vec![(4, 400 * 10_000), (8, 399 * 10_000), (ERA_LENGTH, 399 * 10_000)]
+			);
+
+			// the oldest samples are dropped past `MaxIssuanceSamples`
+			Stp258Standard::on_initialize(12);
+			assert_eq!(Stp258Standard::issuance_history(JUSD).len(), 3);
+			assert_eq!(Stp258Standard::issuance_history(JUSD)[0], (8, 400 * 1_000));
+		});
+}
+
+#[test]
+fn dust_should_be_aggregated_and_redistributed() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let dust_account = Stp258Standard::dust_account(SETT);
+			AggregateDust::<Runtime>::on_dust(&ALICE, SETT, 3);
+			AggregateDust::<Runtime>::on_dust(&BOB, SETT, 4);
+			assert_event(Event::stp258_standard(crate::Event::DustAggregated(SETT, ALICE, 3)));
+			assert_eq!(Stp258Standard::accumulated_dust(SETT), 7);
+			assert_eq!(Stp258Standard::free_balance(SETT, &dust_account), 7);
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 100 * 10_000 - 4);
+
+			Stp258Standard::on_initialize(ERA_LENGTH - 1);
+			assert_eq!(Stp258Standard::accumulated_dust(SETT), 7);
+			Stp258Standard::on_initialize(ERA_LENGTH);
+			assert_event(Event::stp258_standard(crate::Event::DustRedistributed(
+				SETT,
+				7,
+				DustDestination::Treasury,
+			)));
+			assert_eq!(Stp258Standard::accumulated_dust(SETT), 0);
+			assert_eq!(Stp258Standard::free_balance(SETT, &dust_account), 0);
+			assert_eq!(Stp258Standard::free_balance(SETT, &TreasuryAccount::get()), 7);
+		});
+}
+
+#[test]
+fn dust_redistribution_should_be_bounded_and_keep_failures() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			AggregateDust::<Runtime>::on_dust(&ALICE, SETT, 3);
+			// recorded without the funds to send, so redistributing it fails
+			AccumulatedDust::<Runtime>::insert(JUSD, 5);
+
+			Stp258Standard::on_initialize(ERA_LENGTH);
+			// one currency per block, so the pass is still under way
+			assert!(Stp258Standard::dust_redistribution_pass().is_some());
+			Stp258Standard::on_initialize(ERA_LENGTH + 1);
+			Stp258Standard::on_initialize(ERA_LENGTH + 2);
+			assert_eq!(Stp258Standard::dust_redistribution_pass(), None);
+
+			assert_eq!(Stp258Standard::accumulated_dust(SETT), 0);
+			assert_eq!(Stp258Standard::free_balance(SETT, &TreasuryAccount::get()), 3);
+			assert_eq!(Stp258Standard::accumulated_dust(JUSD), 5);
+		});
+}
+
+#[test]
+fn sub_accounts_should_be_registered() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let escrow: AccountId = Stp258ModuleId::get().into_sub_account(SubAccount::EscrowPot(7));
+		assert_eq!(Stp258Standard::sub_account(SubAccount::EscrowPot(7)), escrow);
+		assert_ne!(
+			Stp258Standard::sub_account(SubAccount::EscrowPot(7)),
+			Stp258Standard::sub_account(SubAccount::AuctionPot(7))
+		);
+
+		assert_eq!(Stp258Standard::register_sub_account(SubAccount::EscrowPot(7)), Ok(escrow.clone()));
+		assert_event(Event::stp258_standard(crate::Event::SubAccountRegistered(
+			SubAccount::EscrowPot(7),
+			escrow.clone(),
+		)));
+		assert_noop!(
+			Stp258Standard::register_sub_account(SubAccount::EscrowPot(7)),
+			Error::<Runtime>::SubAccountAlreadyRegistered
+		);
+		assert_eq!(Stp258Standard::sub_accounts(), vec![(SubAccount::EscrowPot(7), escrow)]);
+
+		assert_ok!(Stp258Standard::deregister_sub_account(SubAccount::EscrowPot(7)));
+		assert_noop!(
+			Stp258Standard::deregister_sub_account(SubAccount::EscrowPot(7)),
+			Error::<Runtime>::UnknownSubAccount
+		);
+		assert_eq!(Stp258Standard::sub_accounts(), vec![]);
+	});
+}
+
+#[test]
+fn sub_accounts_should_derive_for_both_account_layouts() {
+	use sp_core::H160;
+	use sp_runtime::traits::BlakeTwo256;
+
+	let module_id = Stp258ModuleId::get();
+	let long_sub = H256::repeat_byte(1);
+	let mut other_long_sub = long_sub;
+	other_long_sub.0[31] = 2;
+
+	// 32-byte accounts keep the `into_sub_account` layout
+	let escrow: AccountId = module_id.into_sub_account(SubAccount::EscrowPot(7));
+	assert_eq!(derive_sub_account::<AccountId, BlakeTwo256>(module_id, SubAccount::EscrowPot(7)), escrow);
+	let deposit: AccountId = module_id.into_sub_account(long_sub);
+	assert_eq!(derive_sub_account::<AccountId, BlakeTwo256>(module_id, long_sub), deposit);
+
+	// 20-byte accounts keep it while the sub-account fits
+	let escrow: H160 = module_id.into_sub_account(SubAccount::EscrowPot(7));
+	assert_eq!(derive_sub_account::<H160, BlakeTwo256>(module_id, SubAccount::EscrowPot(7)), escrow);
+	assert_ne!(
+		derive_sub_account::<H160, BlakeTwo256>(module_id, SubAccount::EscrowPot(7)),
+		derive_sub_account::<H160, BlakeTwo256>(module_id, SubAccount::AuctionPot(7))
+	);
+
+	// and hash longer ones, which `into_sub_account` would cut to 8 bytes
+	let cut = |sub: H256| -> H160 { module_id.into_sub_account(sub) };
+	assert_eq!(cut(long_sub), cut(other_long_sub));
+	assert_ne!(
+		derive_sub_account::<H160, BlakeTwo256>(module_id, long_sub),
+		derive_sub_account::<H160, BlakeTwo256>(module_id, other_long_sub)
+	);
+
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			Stp258Standard::deposit_account_of(&ALICE, 1),
+			module_id.into_sub_account(<Runtime as frame_system::Config>::Hashing::hash_of(&(b"deposit", &ALICE, 1u32)))
+		);
+	});
+}
+
+#[test]
+fn operation_nonces_should_increase_per_account() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_eq!(Stp258Standard::operation_nonce(&ALICE), 0);
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(SETT, &ALICE, &BOB, 10));
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(DNAR, &BOB, &ALICE, 10));
+			assert_event(Event::stp258_standard(crate::Event::Transferred(DNAR, BOB, ALICE, 10, 2, 2)));
+			assert_ok!(<Stp258Standard as Stp258CurrencyExtended<AccountId>>::update_balance(JUSD, &BOB, -5));
+			assert_event(Event::stp258_standard(crate::Event::BalanceUpdated(JUSD, BOB, -5, 3)));
+
+			// failed operations are not counted
+			assert!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(SETT, &SERPER, 1_000 * 10_000).is_err());
+			assert_eq!(Stp258Standard::operation_nonce(&SERPER), 0);
+			assert_eq!(Stp258Standard::operation_nonce(&ALICE), 2);
+		});
+}
+
+#[test]
+fn transfer_all_keep_alive_should_leave_existential_deposit() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::transfer_all_keep_alive(Some(ALICE).into(), BOB, DNAR));
+			assert_transferred(DNAR, &ALICE, &BOB, 99);
+			assert_eq!(Stp258Standard::free_balance(DNAR, &ALICE), 1);
+
+			// locked balance stays behind
+			assert_ok!(Stp258Standard::set_lock(ID_1, SETT, &SERPER, 30 * 10_000));
+			assert_ok!(Stp258Standard::transfer_all_keep_alive(Some(SERPER).into(), BOB, SETT));
+			assert_eq!(Stp258Standard::free_balance(SETT, &SERPER), 30 * 10_000);
+
+			// SETT has no existential deposit
+			assert_ok!(Stp258Standard::transfer_all_keep_alive(Some(SETTPAY).into(), BOB, SETT));
+			assert_eq!(Stp258Standard::free_balance(SETT, &SETTPAY), 0);
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 100 * 10_000 + 70 * 10_000 + 100 * 10_000);
+		});
+}
+
+#[test]
+fn deposit_accounts_should_sweep_to_parent() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let manual = Stp258Standard::deposit_account_of(&ALICE, 1);
+			let swept = Stp258Standard::deposit_account_of(&ALICE, 2);
+			assert_ne!(manual, swept);
+			assert_ne!(manual, Stp258Standard::deposit_account_of(&BOB, 1));
+
+			assert_ok!(Stp258Standard::derive_deposit_account(Some(ALICE).into(), 1, false));
+			assert_event(Event::stp258_standard(crate::Event::DepositAccountDerived(
+				ALICE,
+				1,
+				manual.clone(),
+			)));
+			assert_noop!(
+				Stp258Standard::derive_deposit_account(Some(ALICE).into(), 1, true),
+				Error::<Runtime>::DepositAccountExists
+			);
+			assert_ok!(Stp258Standard::derive_deposit_account(Some(ALICE).into(), 2, true));
+
+			assert_ok!(Stp258Standard::transfer(Some(BOB).into(), manual.clone(), SETT, 30));
+			assert_eq!(Stp258Standard::free_balance(SETT, &manual), 30);
+			assert_noop!(
+				Stp258Standard::sweep_deposit_account(Some(BOB).into(), manual.clone(), SETT),
+				Error::<Runtime>::NotDepositAccountOwner
+			);
+			assert_ok!(Stp258Standard::sweep_deposit_account(Some(ALICE).into(), manual.clone(), SETT));
+			assert_event(Event::stp258_standard(crate::Event::DepositAccountSwept(SETT, manual.clone(), 30)));
+			assert_eq!(Stp258Standard::free_balance(SETT, &manual), 0);
+
+			assert_ok!(Stp258Standard::transfer(Some(BOB).into(), swept.clone(), SETT, 40));
+			assert_eq!(Stp258Standard::free_balance(SETT, &swept), 0);
+			assert_eq!(Stp258Standard::free_balance(SETT, &ALICE), 100 * 10_000 + 70);
+		});
+}
+
+#[test]
+fn balances_of_should_be_bounded() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(JUSD, &ALICE, &BOB, 10));
+			let charlie = AccountId::new([5u8; 32]);
+			assert_eq!(
+				Stp258Standard::balances_of(JUSD, &[ALICE, charlie.clone(), BOB]),
+				vec![100 * 1_000 - 10, 0, 100 * 1_000 + 10]
+			);
+			assert_eq!(
+				Stp258Standard::balances_of(JUSD, &[ALICE, BOB, SERPER, SETTPAY]).len() as u32,
+				MaxBalancesPerQuery::get()
+			);
+		});
+}
+
+#[test]
+fn circulating_supply_should_exclude_protocol_accounts_and_locks() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_eq!(Stp258Standard::circulating_supply(JUSD), 400 * 1_000);
+			let pot = Stp258Standard::register_sub_account(SubAccount::SerpPot).unwrap();
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::transfer(JUSD, &ALICE, &pot, 50 * 1_000));
+			assert_ok!(Stp258Standard::set_lock(ID_1, JUSD, &pot, 20 * 1_000));
+			assert_ok!(Stp258Standard::set_lock(ID_1, JUSD, &BOB, 30 * 1_000));
+			assert_eq!(Stp258Standard::circulating_supply(JUSD), 400 * 1_000 - 50 * 1_000 - 30 * 1_000);
+		});
+}
+
+#[test]
+fn observers_should_see_monetary_flows() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::deposit(SETT, &ALICE, 10));
+			assert_ok!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(JUSD, &BOB, 20));
+			assert!(<Stp258Standard as Stp258Currency<AccountId>>::withdraw(JUSD, &BOB, 1_000 * 1_000).is_err());
+			assert_eq!(Stp258Standard::slash(SETT, &SERPER, 30), 0);
+			assert_eq!(
+				recorded_flows(),
+				vec![
+					("deposit", SETT, ALICE, 10),
+					("withdraw", JUSD, BOB, 20),
+					("slash", SETT, SERPER, 30),
+				]
+			);
+		});
+}
+
+#[test]
+fn observers_should_see_balance_updates_and_slash_credits() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let treasury = TreasuryAccount::get();
+			assert_ok!(<Stp258Standard as Stp258CurrencyExtended<AccountId>>::update_balance(JUSD, &BOB, 5));
+			assert_ok!(<Stp258Standard as Stp258CurrencyExtended<AccountId>>::update_balance(JUSD, &BOB, -3));
+			assert_ok!(Stp258Standard::governed_adjust_supply(
+				Origin::root(),
+				JUSD,
+				7,
+				H256::repeat_byte(1)
+			));
+			assert_ok!(Stp258Standard::set_slash_destination(
+				Origin::root(),
+				SETT,
+				SlashDestination::Treasury
+			));
+			assert_eq!(Stp258Standard::slash(SETT, &SERPER, 30), 0);
+			assert_eq!(
+				recorded_flows(),
+				vec![
+					("deposit", JUSD, BOB, 5),
+					("withdraw", JUSD, BOB, 3),
+					("deposit", JUSD, treasury.clone(), 7),
+					("deposit", SETT, treasury.clone(), 30),
+					("slash", SETT, SERPER, 30),
+				]
+			);
+		});
+}
+
+#[test]
+fn observers_should_see_fees_charged_on_transfer() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let treasury = TreasuryAccount::get();
+			assert_ok!(Stp258Standard::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert!(recorded_flows().is_empty());
+
+			System::set_block_number(11);
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_eq!(
+				recorded_flows(),
+				vec![
+					("withdraw", SETT, ALICE, 9 * 10_000),
+					("deposit", SETT, treasury.clone(), 9 * 10_000),
+					("withdraw", SETT, BOB, 11 * 10_000),
+					("deposit", SETT, treasury.clone(), 11 * 10_000),
+				]
+			);
+		});
+}
+
+#[test]
+fn pending_transfer_should_outlive_its_guard() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_transfer_guard(Some(ALICE).into(), SETT, SERPER, 50));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 60));
+			assert_ok!(Stp258Standard::transfer(Some(ALICE).into(), BOB, SETT, 70));
+			assert_ok!(Stp258Standard::remove_transfer_guard(Some(SERPER).into(), ALICE, SETT));
+
+			// the guardian the transfer was queued under can still confirm it
+			assert_noop!(
+				Stp258Standard::confirm_transfer(Some(BOB).into(), 0),
+				Error::<Runtime>::NotGuardian
+			);
+			assert_ok!(Stp258Standard::confirm_transfer(Some(SERPER).into(), 0));
+			assert_transferred(SETT, &ALICE, &BOB, 60);
+
+			// anyone may drop a lapsed transfer
+			System::set_block_number(1 + TransferConfirmationWindow::get() + 1);
+			assert_ok!(Stp258Standard::confirm_transfer(Some(BOB).into(), 1));
+			assert_event(Event::stp258_standard(crate::Event::TransferLapsed(1)));
+			assert_eq!(Stp258Standard::pending_transfer(1), None);
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 100 * 10_000 + 60);
+		});
+}
+
+#[test]
+fn delegated_transfer_should_respect_transfer_guard() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Standard::set_transfer_guard(Some(ALICE).into(), SETT, SERPER, 50));
+			assert_ok!(Stp258Standard::add_delegate(Some(ALICE).into(), SETT, BOB, 100));
+
+			assert_ok!(Stp258Standard::delegated_transfer(Some(BOB).into(), ALICE, SETTPAY, SETT, 60));
+			assert_event(Event::stp258_standard(crate::Event::TransferAwaitingConfirmation(
+				0, SETT, ALICE, SETTPAY, 60,
+			)));
+			assert_eq!(Stp258Standard::free_balance(SETT, &SETTPAY), 100 * 10_000);
+			assert_eq!(Stp258Standard::delegation(&ALICE, (SETT, BOB)).unwrap().spent, 60);
+
+			assert_ok!(Stp258Standard::confirm_transfer(Some(SERPER).into(), 0));
+			assert_eq!(Stp258Standard::free_balance(SETT, &SETTPAY), 100 * 10_000 + 60);
+		});
+}
+
+#[test]
+fn instances_should_keep_separate_storage() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258StandardInstance1::set_lock(ID_1, SETT, &ALICE, 30));
+			assert_ok!(Stp258StandardInstance1::hold(HoldReason::Escrow, SETT, &ALICE, 20));
+			assert_ok!(<Stp258StandardInstance1 as Stp258Currency<AccountId>>::transfer(SETT, &ALICE, &BOB, 10));
+			assert_ok!(Stp258StandardInstance1::set_demurrage_rate(
+				Origin::root(),
+				SETT,
+				Some(Perbill::from_percent(1))
+			));
+			assert_eq!(Stp258StandardInstance1::total_locked(SETT), 30);
+			assert_eq!(Stp258StandardInstance1::total_reserved(SETT), 20);
+			assert_eq!(Stp258StandardInstance1::operation_nonce(&ALICE), 1);
+
+			// the default instance records none of it
+			assert!(Stp258Standard::locks(&ALICE, SETT).is_empty());
+			assert_eq!(Stp258Standard::total_locked(SETT), 0);
+			assert_eq!(Stp258Standard::total_balance_on_hold(SETT, &ALICE), 0);
+			assert_eq!(Stp258Standard::total_reserved(SETT), 0);
+			assert_eq!(Stp258Standard::operation_nonce(&ALICE), 0);
+			assert_eq!(Stp258Standard::demurrage_rate(SETT), None);
+
+			// each instance derives its own accounts
+			assert_ne!(Stp258Standard::dust_account(SETT), Stp258StandardInstance1::dust_account(SETT));
+			assert_ne!(
+				Stp258Standard::deposit_account_of(&ALICE, 1),
+				Stp258StandardInstance1::deposit_account_of(&ALICE, 1)
+			);
+
+			// while both front the same backing currencies
+			assert_eq!(Stp258Standard::free_balance(SETT, &BOB), 100 * 10_000 + 10);
+			assert_eq!(Stp258StandardInstance1::free_balance(SETT, &BOB), 100 * 10_000 + 10);
 		});
 }
@@ -0,0 +1,91 @@
+//! Conversions between human-readable amounts and on-chain balances.
+//!
+//! A currency's whole unit is `Stp258Currency::base_unit` on-chain units,
+//! so tooling and pallets need not hard-code powers of ten per currency.
+
+use super::*;
+
+/// `whole` units of `currency_id` as an on-chain balance, `None` on
+/// overflow.
+pub fn whole_to_base_units<T: Config<I>, I: 'static>(
+	currency_id: CurrencyIdOf<T, I>,
+	whole: BalanceOf<T, I>,
+) -> Option<BalanceOf<T, I>> {
+	whole.checked_mul(&<Pallet<T, I> as Stp258Currency<T::AccountId>>::base_unit(currency_id))
+}
+
+/// `balance` of `currency_id` as whole units and the on-chain units left
+/// over.
+pub fn to_whole_units<T: Config<I>, I: 'static>(
+	currency_id: CurrencyIdOf<T, I>,
+	balance: BalanceOf<T, I>,
+) -> (BalanceOf<T, I>, BalanceOf<T, I>) {
+	let base_unit = <Pallet<T, I> as Stp258Currency<T::AccountId>>::base_unit(currency_id);
+	if base_unit.is_zero() {
+		return (Zero::zero(), balance);
+	}
+	(balance / base_unit, balance % base_unit)
+}
+
+/// The number of decimals of `currency_id`, `None` if its base unit is not
+/// a power of ten.
+pub fn decimals<T: Config<I>, I: 'static>(currency_id: CurrencyIdOf<T, I>) -> Option<u32> {
+	let mut base_unit: u128 = <Pallet<T, I> as Stp258Currency<T::AccountId>>::base_unit(currency_id).saturated_into();
+	let mut decimals = 0;
+	while base_unit > 1 {
+		if base_unit % 10 != 0 {
+			return None;
+		}
+		base_unit /= 10;
+		decimals += 1;
+	}
+	if base_unit == 1 {
+		Some(decimals)
+	} else {
+		None
+	}
+}
+
+/// The decimal `amount` of `currency_id`, e.g. `"12.34"`, as an on-chain
+/// balance.
+///
+/// Returns `None` if `amount` is not a plain decimal, has more fractional
+/// digits than the currency's decimals, or overflows the balance.
+#[cfg(feature = "std")]
+pub fn to_base_units<T: Config<I>, I: 'static>(currency_id: CurrencyIdOf<T, I>, amount: &str) -> Option<BalanceOf<T, I>> {
+	let decimals = decimals::<T, I>(currency_id)? as usize;
+	let (whole, fraction) = match amount.find('.') {
+		Some(point) => (&amount[..point], &amount[point + 1..]),
+		None => (amount, ""),
+	};
+	let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+	if whole.is_empty() || !digits(whole) || !digits(fraction) || fraction.len() > decimals {
+		return None;
+	}
+	let units: u128 = format!("{}{:0<width$}", whole, fraction, width = decimals).parse().ok()?;
+	BalanceOf::<T, I>::try_from(units).ok()
+}
+
+/// `balance` of `currency_id` as a decimal string, e.g. `"12.34"`.
+///
+/// Returns `None` if the currency's base unit is not a power of ten.
+#[cfg(feature = "std")]
+pub fn from_base_units<T: Config<I>, I: 'static>(
+	currency_id: CurrencyIdOf<T, I>,
+	balance: BalanceOf<T, I>,
+) -> Option<String> {
+	let decimals = decimals::<T, I>(currency_id)? as usize;
+	let (whole, fraction) = to_whole_units::<T, I>(currency_id, balance);
+	let whole: u128 = whole.saturated_into();
+	if decimals == 0 {
+		return Some(whole.to_string());
+	}
+	let fraction: u128 = fraction.saturated_into();
+	let fraction = format!("{:0>width$}", fraction, width = decimals);
+	let fraction = fraction.trim_end_matches('0');
+	if fraction.is_empty() {
+		Some(whole.to_string())
+	} else {
+		Some(format!("{}.{}", whole, fraction))
+	}
+}